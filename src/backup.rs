@@ -17,10 +17,23 @@
 use {DB, Error};
 use ffi;
 
-use libc::{c_int, uint32_t};
+use libc::c_int;
 use std::ffi::CString;
 use std::path::Path;
 
+/// Information about a single backup held by a `BackupEngine`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupEngineInfo {
+    /// ID of the backup. IDs are always increasing.
+    pub backup_id: u32,
+    /// Time the backup was taken, in seconds since the epoch.
+    pub timestamp: i64,
+    /// Size of the backup in bytes. Files shared with other backups are counted in full.
+    pub size: u64,
+    /// Number of files that make up the backup.
+    pub num_files: u32,
+}
+
 pub struct BackupEngine {
     inner: *mut ffi::rocksdb_backup_engine_t,
 }
@@ -75,11 +88,85 @@ impl BackupEngine {
         unsafe {
             ffi_try!(ffi::rocksdb_backup_engine_purge_old_backups(
                 self.inner,
-                num_backups_to_keep as uint32_t,
+                num_backups_to_keep as u32,
             ));
             Ok(())
         }
     }
+
+    /// Restores the database at `db_dir` from the most recent backup, writing the
+    /// restored write-ahead log files to `wal_dir`.
+    ///
+    /// The database must not be open while it is being restored.
+    ///
+    /// There is no way to restore an older backup by id, as the C API of the
+    /// pinned librocksdb-sys (RocksDB 6.1) lacks
+    /// `rocksdb_backup_engine_restore_db_from_backup`.
+    pub fn restore_from_latest_backup<D: AsRef<Path>, W: AsRef<Path>>(
+        &mut self,
+        db_dir: D,
+        wal_dir: W,
+        opts: &RestoreOptions,
+    ) -> Result<(), Error> {
+        let c_db_dir = try!(to_cpath(db_dir.as_ref()));
+        let c_wal_dir = try!(to_cpath(wal_dir.as_ref()));
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_backup_engine_restore_db_from_latest_backup(
+                self.inner,
+                c_db_dir.as_ptr(),
+                c_wal_dir.as_ptr(),
+                opts.inner,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that every file of the backup exists and has the expected size.
+    ///
+    /// File checksums are not verified.
+    pub fn verify_backup(&self, backup_id: u32) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_backup_engine_verify_backup(
+                self.inner,
+                backup_id,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the list of backups held by this engine, oldest first.
+    ///
+    /// The sum of the reported sizes may exceed the size of the backup directory,
+    /// as files can be shared between backups.
+    pub fn get_backup_info(&self) -> Vec<BackupEngineInfo> {
+        unsafe {
+            let info = ffi::rocksdb_backup_engine_get_backup_info(self.inner);
+            let count = ffi::rocksdb_backup_engine_info_count(info);
+
+            let mut backups = Vec::with_capacity(count as usize);
+            for index in 0..count {
+                backups.push(BackupEngineInfo {
+                    backup_id: ffi::rocksdb_backup_engine_info_backup_id(info, index),
+                    timestamp: ffi::rocksdb_backup_engine_info_timestamp(info, index),
+                    size: ffi::rocksdb_backup_engine_info_size(info, index),
+                    num_files: ffi::rocksdb_backup_engine_info_number_files(info, index),
+                });
+            }
+            ffi::rocksdb_backup_engine_info_destroy(info);
+            backups
+        }
+    }
+}
+
+fn to_cpath(path: &Path) -> Result<CString, Error> {
+    match CString::new(path.to_string_lossy().as_bytes()) {
+        Ok(c) => Ok(c),
+        Err(_) => Err(Error::new(format!(
+            "Failed to convert path {:?} to CString",
+            path
+        ))),
+    }
 }
 
 impl BackupEngineOptions {
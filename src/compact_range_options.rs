@@ -0,0 +1,79 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use {BottommostLevelCompaction, CompactRangeOptions};
+use libc::{c_int, c_uchar};
+
+impl Drop for CompactRangeOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_compactoptions_destroy(self.inner);
+        }
+    }
+}
+
+impl CompactRangeOptions {
+    pub fn new() -> CompactRangeOptions {
+        CompactRangeOptions::default()
+    }
+
+    /// If true, no other compaction runs at the same time as this one.
+    ///
+    /// Default: true
+    pub fn set_exclusive_manual_compaction(&mut self, exclusive: bool) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_exclusive_manual_compaction(self.inner, exclusive as c_uchar);
+        }
+    }
+
+    /// Sets whether files already in the bottommost level are compacted again.
+    ///
+    /// Default: `BottommostLevelCompaction::IfHaveCompactionFilter`
+    pub fn set_bottommost_level_compaction(&mut self, mode: BottommostLevelCompaction) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_bottommost_level_compaction(self.inner, mode as c_uchar);
+        }
+    }
+
+    /// If true, the compacted files are moved to the level set with
+    /// `set_target_level`.
+    ///
+    /// Default: false
+    pub fn set_change_level(&mut self, change: bool) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_change_level(self.inner, change as c_uchar);
+        }
+    }
+
+    /// The level compacted files are moved to when `set_change_level` is set.
+    /// A negative value means the lowest level that can hold the data.
+    ///
+    /// Default: -1
+    pub fn set_target_level(&mut self, level: i32) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_target_level(self.inner, level as c_int);
+        }
+    }
+}
+
+impl Default for CompactRangeOptions {
+    fn default() -> CompactRangeOptions {
+        let compact_opts = unsafe { ffi::rocksdb_compactoptions_create() };
+        if compact_opts.is_null() {
+            panic!("Could not create RocksDB compact range options");
+        }
+        CompactRangeOptions { inner: compact_opts }
+    }
+}
@@ -14,6 +14,7 @@
 //
 
 use libc::{c_char, c_int, c_uchar, c_void, size_t};
+use ffi;
 use std::ffi::CString;
 use std::mem;
 use std::slice;
@@ -29,26 +30,75 @@ pub enum Decision {
     /// Remove the object from the database
     Remove,
     /// Change the value for the key
-    Change(&'static [u8]),
+    Change(Vec<u8>),
 }
 
 
 /// Function to filter compaction with.
 ///
-/// This function takes the level of compaction, the key, and the existing value
-/// and returns the decision about how to handle the Key-Value pair.
+/// This function takes the level of compaction, the key, the existing value and
+/// whether the compaction was requested manually (for example through
+/// `DB::compact_range`), and returns the decision about how to handle the
+/// Key-Value pair.
 ///
 ///  See [Options::set_compaction_filter][set_compaction_filter] for more details
 ///
 ///  [set_compaction_filter]: ../struct.Options.html#method.set_compaction_filter
-pub trait CompactionFilterFn: FnMut(u32, &[u8], &[u8]) -> Decision {}
+pub trait CompactionFilterFn: Fn(u32, &[u8], &[u8], bool) -> Decision + Send + Sync + 'static {}
 impl<F> CompactionFilterFn for F
 where
-    F: FnMut(u32, &[u8], &[u8]) -> Decision,
-    F: Send + 'static,
+    F: Fn(u32, &[u8], &[u8], bool) -> Decision,
+    F: Send + Sync + 'static,
 {
 }
 
+/// A compaction filter built by a [`CompactionFilterFactory`] for a single
+/// compaction job.
+///
+/// A filter is only used by the thread running its compaction, so it can keep
+/// state across calls without any locking.
+///
+/// [`CompactionFilterFactory`]: trait.CompactionFilterFactory.html
+pub trait CompactionFilter {
+    /// Takes the level of compaction, the key and the existing value, and returns
+    /// the decision about how to handle the Key-Value pair.
+    fn filter(&mut self, level: u32, key: &[u8], value: &[u8]) -> Decision;
+}
+
+/// Details about the compaction a filter is being created for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactionFilterContext {
+    /// Whether every data file is part of the compaction.
+    pub is_full_compaction: bool,
+    /// Whether the compaction was requested manually, e.g. by `DB::compact_range`.
+    pub is_manual_compaction: bool,
+}
+
+impl CompactionFilterContext {
+    unsafe fn from_raw(raw: *mut ffi::rocksdb_compactionfiltercontext_t) -> CompactionFilterContext {
+        CompactionFilterContext {
+            is_full_compaction: ffi::rocksdb_compactionfiltercontext_is_full_compaction(raw) != 0,
+            is_manual_compaction: ffi::rocksdb_compactionfiltercontext_is_manual_compaction(raw) != 0,
+        }
+    }
+}
+
+/// Builds a new [`CompactionFilter`] for every compaction job.
+///
+/// Compactions may run in parallel, so `create` can be called from several
+/// threads at once.
+///
+///  See [Options::set_compaction_filter_factory][set_compaction_filter_factory] for more details
+///
+///  [`CompactionFilter`]: trait.CompactionFilter.html
+///  [set_compaction_filter_factory]: ../struct.Options.html#method.set_compaction_filter_factory
+pub trait CompactionFilterFactory: Send + Sync + 'static {
+    type Filter: CompactionFilter;
+
+    /// Returns the filter to use for the compaction described by `context`.
+    fn create(&self, context: CompactionFilterContext) -> Self::Filter;
+}
+
 pub struct CompactionFilterCallback<F>
 where
     F: CompactionFilterFn,
@@ -57,6 +107,63 @@ where
     pub filter_fn: F,
 }
 
+/// Filter built from the `CompactionFilterFn` given to `Options::set_compaction_filter`.
+struct FnFilter<F>
+where
+    F: CompactionFilterFn,
+{
+    cb: *const CompactionFilterCallback<F>,
+    is_manual_compaction: bool,
+}
+
+impl<F> CompactionFilter for FnFilter<F>
+where
+    F: CompactionFilterFn,
+{
+    fn filter(&mut self, level: u32, key: &[u8], value: &[u8]) -> Decision {
+        // Shared with the filters of concurrent compactions.
+        let cb = unsafe { &*self.cb };
+        (cb.filter_fn)(level, key, value, self.is_manual_compaction)
+    }
+}
+
+pub struct CompactionFilterFactoryCallback<T>
+where
+    T: CompactionFilterFactory,
+{
+    pub name: CString,
+    pub factory: T,
+}
+
+/// The filter built for a single compaction job, along with the state needed
+/// to hand its decisions over to RocksDB.
+struct CompactionFilterState<C>
+where
+    C: CompactionFilter,
+{
+    name: *const c_char,
+    filter: C,
+    // Holds the value of the last `Change` decision until RocksDB has copied it.
+    new_value: Vec<u8>,
+}
+
+unsafe fn create_filter<C>(name: *const c_char, filter: C) -> *mut ffi::rocksdb_compactionfilter_t
+where
+    C: CompactionFilter,
+{
+    let state = Box::new(CompactionFilterState {
+        name: name,
+        filter: filter,
+        new_value: Vec::new(),
+    });
+    ffi::rocksdb_compactionfilter_create(
+        mem::transmute(state),
+        Some(state_destructor_callback::<C>),
+        Some(filter_callback::<C>),
+        Some(state_name_callback::<C>),
+    )
+}
+
 pub unsafe extern "C" fn destructor_callback<F>(raw_cb: *mut c_void)
 where
     F: CompactionFilterFn,
@@ -72,8 +179,65 @@ where
     cb.name.as_ptr()
 }
 
-pub unsafe extern "C" fn filter_callback<F>(
+pub unsafe extern "C" fn create_filter_callback<F>(
+    raw_cb: *mut c_void,
+    context: *mut ffi::rocksdb_compactionfiltercontext_t,
+) -> *mut ffi::rocksdb_compactionfilter_t
+where
+    F: CompactionFilterFn,
+{
+    let cb = raw_cb as *const CompactionFilterCallback<F>;
+    let filter = FnFilter {
+        cb: cb,
+        is_manual_compaction: ffi::rocksdb_compactionfiltercontext_is_manual_compaction(context) != 0,
+    };
+    create_filter((*cb).name.as_ptr(), filter)
+}
+
+pub unsafe extern "C" fn factory_destructor_callback<T>(raw_cb: *mut c_void)
+where
+    T: CompactionFilterFactory,
+{
+    let _: Box<CompactionFilterFactoryCallback<T>> = mem::transmute(raw_cb);
+}
+
+pub unsafe extern "C" fn factory_name_callback<T>(raw_cb: *mut c_void) -> *const c_char
+where
+    T: CompactionFilterFactory,
+{
+    let cb = &*(raw_cb as *mut CompactionFilterFactoryCallback<T>);
+    cb.name.as_ptr()
+}
+
+pub unsafe extern "C" fn factory_create_callback<T>(
     raw_cb: *mut c_void,
+    context: *mut ffi::rocksdb_compactionfiltercontext_t,
+) -> *mut ffi::rocksdb_compactionfilter_t
+where
+    T: CompactionFilterFactory,
+{
+    let cb = &*(raw_cb as *mut CompactionFilterFactoryCallback<T>);
+    let filter = cb.factory.create(CompactionFilterContext::from_raw(context));
+    create_filter(cb.name.as_ptr(), filter)
+}
+
+unsafe extern "C" fn state_destructor_callback<C>(raw_state: *mut c_void)
+where
+    C: CompactionFilter,
+{
+    let _: Box<CompactionFilterState<C>> = mem::transmute(raw_state);
+}
+
+unsafe extern "C" fn state_name_callback<C>(raw_state: *mut c_void) -> *const c_char
+where
+    C: CompactionFilter,
+{
+    let state = &*(raw_state as *mut CompactionFilterState<C>);
+    state.name
+}
+
+unsafe extern "C" fn filter_callback<C>(
+    raw_state: *mut c_void,
     level: c_int,
     raw_key: *const c_char,
     key_length: size_t,
@@ -84,20 +248,21 @@ pub unsafe extern "C" fn filter_callback<F>(
     value_changed: *mut c_uchar,
 ) -> c_uchar
 where
-    F: CompactionFilterFn,
+    C: CompactionFilter,
 {
     use self::Decision::*;
 
-    let cb = &mut *(raw_cb as *mut CompactionFilterCallback<F>);
+    let state = &mut *(raw_state as *mut CompactionFilterState<C>);
     let key = slice::from_raw_parts(raw_key as *const u8, key_length as usize);
     let oldval = slice::from_raw_parts(existing_value as *const u8, value_length as usize);
-    let result = (cb.filter_fn)(level as u32, key, oldval);
+    let result = state.filter.filter(level as u32, key, oldval);
     match result {
         Keep => 0,
         Remove => 1,
         Change(newval) => {
-            *new_value = newval.as_ptr() as *mut c_char;
-            *new_value_length = newval.len() as size_t;
+            state.new_value = newval;
+            *new_value = state.new_value.as_ptr() as *mut c_char;
+            *new_value_length = state.new_value.len() as size_t;
             *value_changed = 1 as c_uchar;
             0
         }
@@ -106,11 +271,12 @@ where
 
 #[cfg(test)]
 #[allow(unused_variables)]
-fn test_filter(level: u32, key: &[u8], value: &[u8]) -> Decision {
+fn test_filter(level: u32, key: &[u8], value: &[u8], manual: bool) -> Decision {
     use self::Decision::*;
     match key.first() {
         Some(&b'_') => Remove,
-        Some(&b'%') => Change(b"secret"),
+        Some(&b'%') => Change(b"secret".to_vec()),
+        Some(&b'#') => Change([value, b"-rewritten"].concat()),
         _ => Keep,
     }
 }
@@ -128,10 +294,99 @@ fn compaction_filter_test() {
         let _ = db.put(b"k1", b"a");
         let _ = db.put(b"_k", b"b");
         let _ = db.put(b"%k", b"c");
+        let _ = db.put(b"#k", b"d");
         db.compact_range(None, None);
         assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"a");
         assert!(db.get(b"_k").unwrap().is_none());
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
+        assert_eq!(&*db.get(b"#k").unwrap().unwrap(), b"d-rewritten");
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+fn compaction_filter_manual_test() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use {DB, Options};
+
+    let path = "_rust_rocksdb_filter_manual_test";
+    let seen_manual = Arc::new(AtomicBool::new(false));
+    let flag = seen_manual.clone();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compaction_filter("manual", move |_level: u32, _key: &[u8], _value: &[u8], manual: bool| {
+        if manual {
+            flag.store(true, Ordering::SeqCst);
+        }
+        Decision::Keep
+    });
+    {
+        let db = DB::open(&opts, path).unwrap();
+        let _ = db.put(b"k1", b"a");
+        db.compact_range(None, None);
     }
+    assert!(seen_manual.load(Ordering::SeqCst));
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[cfg(test)]
+struct CountingFilter {
+    seen: usize,
+}
+
+#[cfg(test)]
+impl CompactionFilter for CountingFilter {
+    fn filter(&mut self, _level: u32, _key: &[u8], _value: &[u8]) -> Decision {
+        self.seen += 1;
+        // keep only the first two keys of every compaction job
+        if self.seen > 2 {
+            Decision::Remove
+        } else {
+            Decision::Keep
+        }
+    }
+}
+
+// Records the contexts it is given, to be checked once the compaction is done:
+// a failed assertion inside a RocksDB callback would abort the process.
+#[cfg(test)]
+struct CountingFactory {
+    contexts: ::std::sync::Arc<::std::sync::Mutex<Vec<CompactionFilterContext>>>,
+}
+
+#[cfg(test)]
+impl CompactionFilterFactory for CountingFactory {
+    type Filter = CountingFilter;
 
+    fn create(&self, context: CompactionFilterContext) -> CountingFilter {
+        self.contexts.lock().unwrap().push(context);
+        CountingFilter { seen: 0 }
+    }
+}
+
+#[test]
+fn compaction_filter_factory_test() {
+    use std::sync::{Arc, Mutex};
+    use {DB, Options};
+
+    let path = "_rust_rocksdb_filter_factory_test";
+    let contexts = Arc::new(Mutex::new(Vec::new()));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compaction_filter_factory("counting", CountingFactory { contexts: contexts.clone() });
+    {
+        let db = DB::open(&opts, path).unwrap();
+        let _ = db.put(b"k1", b"a");
+        let _ = db.put(b"k2", b"b");
+        let _ = db.put(b"k3", b"c");
+        db.compact_range(None, None);
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(db.get(b"k2").unwrap().is_some());
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+    let contexts = contexts.lock().unwrap();
+    assert!(!contexts.is_empty());
+    assert!(contexts.iter().all(|context| context.is_manual_compaction));
+    assert!(DB::destroy(&opts, path).is_ok());
 }
@@ -20,31 +20,70 @@ use std::ffi::CString;
 use std::mem;
 use std::slice;
 
-pub type CompareFn = fn(&[u8], &[u8]) -> Ordering;
+/// Function used to order keys.
+///
+/// Any `Fn` closure can be used, so a comparator may capture state such as a
+/// collation table. It is called concurrently from many threads.
+///
+///  See [Options::set_comparator][set_comparator] for more details
+///
+///  [set_comparator]: ../struct.Options.html#method.set_comparator
+pub trait CompareFn: Fn(&[u8], &[u8]) -> Ordering + Send + Sync + 'static {}
+impl<F> CompareFn for F
+where
+    F: Fn(&[u8], &[u8]) -> Ordering,
+    F: Send + Sync + 'static,
+{
+}
+
+/// Orders keys by their bytes, in descending order.
+pub fn reverse_bytewise(a: &[u8], b: &[u8]) -> Ordering {
+    b.cmp(a)
+}
+
+/// Orders keys by a big-endian `u64` stored in their first 8 bytes, then by
+/// the remaining bytes.
+///
+/// Keys shorter than 8 bytes sort before all others, bytewise among themselves.
+pub fn u64_big_endian_prefix(a: &[u8], b: &[u8]) -> Ordering {
+    fn prefix(key: &[u8]) -> Option<u64> {
+        if key.len() < 8 {
+            return None;
+        }
+        Some(key[..8].iter().fold(0, |n, &byte| (n << 8) | u64::from(byte)))
+    }
 
-pub struct ComparatorCallback {
+    match (prefix(a), prefix(b)) {
+        (Some(x), Some(y)) => x.cmp(&y).then_with(|| a[8..].cmp(&b[8..])),
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+pub struct ComparatorCallback<F: CompareFn> {
     pub name: CString,
-    pub f: CompareFn,
+    pub f: F,
 }
 
-pub unsafe extern "C" fn destructor_callback(raw_cb: *mut c_void) {
-    let _: Box<ComparatorCallback> = mem::transmute(raw_cb);
+pub unsafe extern "C" fn destructor_callback<F: CompareFn>(raw_cb: *mut c_void) {
+    let _: Box<ComparatorCallback<F>> = mem::transmute(raw_cb);
 }
 
-pub unsafe extern "C" fn name_callback(raw_cb: *mut c_void) -> *const c_char {
-    let cb: &mut ComparatorCallback = &mut *(raw_cb as *mut ComparatorCallback);
+pub unsafe extern "C" fn name_callback<F: CompareFn>(raw_cb: *mut c_void) -> *const c_char {
+    let cb: &ComparatorCallback<F> = &*(raw_cb as *const ComparatorCallback<F>);
     let ptr = cb.name.as_ptr();
     ptr as *const c_char
 }
 
-pub unsafe extern "C" fn compare_callback(
+pub unsafe extern "C" fn compare_callback<F: CompareFn>(
     raw_cb: *mut c_void,
     a_raw: *const c_char,
     a_len: size_t,
     b_raw: *const c_char,
     b_len: size_t,
 ) -> c_int {
-    let cb: &mut ComparatorCallback = &mut *(raw_cb as *mut ComparatorCallback);
+    let cb: &ComparatorCallback<F> = &*(raw_cb as *const ComparatorCallback<F>);
     let a: &[u8] = slice::from_raw_parts(a_raw as *const u8, a_len as usize);
     let b: &[u8] = slice::from_raw_parts(b_raw as *const u8, b_len as usize);
     match (cb.f)(a, b) {
@@ -53,3 +92,13 @@ pub unsafe extern "C" fn compare_callback(
         Ordering::Greater => 1,
     }
 }
+
+#[test]
+fn u64_big_endian_prefix_test() {
+    let one = [0, 0, 0, 0, 0, 0, 0, 1, b'z'];
+    let two = [0, 0, 0, 0, 0, 0, 0, 2, b'a'];
+    assert_eq!(u64_big_endian_prefix(&one, &two), Ordering::Less);
+    assert_eq!(u64_big_endian_prefix(&two[..8], &two), Ordering::Less);
+    assert_eq!(u64_big_endian_prefix(b"short", &one), Ordering::Less);
+    assert_eq!(reverse_bytewise(b"a", b"b"), Ordering::Greater);
+}
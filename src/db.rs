@@ -13,25 +13,37 @@
 // limitations under the License.
 //
 
-use {DB, Error, Options, WriteOptions, ColumnFamily, ColumnFamilyDescriptor, DBRawIterator, Direction, WriteBatch, ReadOptions, Snapshot, DBIterator, DBVector, IteratorMode};
+use {DB, CompactRangeOptions, Error, ErrorKind, FlushOptions, IngestExternalFileOptions, Options, WriteOptions, ColumnFamily, ColumnFamilyDescriptor, DBRawIterator, Direction, WriteBatch, ReadOptions, Snapshot, DBIterator, DBVector, IteratorMode};
 use ffi;
-use ffi_util::opt_bytes_to_ptr;
+use ffi_util::{keys_to_ptrs, multi_get_results, open_column_families, opt_bytes_to_ptr,
+               paths_to_cstrings, property_name, property_value};
 
-use libc::{c_char, c_int, size_t};
+use libc::{c_char, c_int, c_uchar, size_t};
+use std::cmp;
 use std::collections::BTreeMap;
 use std::ffi::CString;
 use std::fmt;
 use std::fs;
+use std::ops::{Bound, RangeBounds};
 use std::path::Path;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::time::Duration;
 use std::ffi::CStr;
 
 pub fn new_bloom_filter(bits: c_int) -> *mut ffi::rocksdb_filterpolicy_t {
     unsafe { ffi::rocksdb_filterpolicy_create_bloom(bits) }
 }
 
+/// How `DB::open_cf_descriptors_internal` opens the database.
+enum AccessType {
+    ReadWrite,
+    ReadOnly { error_if_log_file_exist: bool },
+    /// Only opened without column families, see `DB::open_with_ttl`.
+    WithTTL { ttl: Duration },
+}
+
 unsafe impl Send for DB {}
 unsafe impl Sync for DB {}
 
@@ -59,6 +71,66 @@ impl DB {
 
     /// Open a database with the given database options and column family names/options.
     pub fn open_cf_descriptors<P: AsRef<Path>>(opts: &Options, path: P, cfs: Vec<ColumnFamilyDescriptor>) -> Result<DB, Error> {
+        DB::open_cf_descriptors_internal(opts, path, cfs, AccessType::ReadWrite)
+    }
+
+    /// Open a database in TTL mode: entries older than `ttl` are dropped during
+    /// compaction. They may still be read until they are compacted away.
+    ///
+    /// RocksDB counts the TTL in whole seconds, so any fraction of a second is
+    /// dropped, and a non-zero `ttl` under one second is an `InvalidArgument`
+    /// error. A zero `ttl` keeps entries forever.
+    ///
+    /// A TTL database must always be opened with a TTL, as its values carry a
+    /// timestamp that a plain `DB::open` would return as part of the value.
+    ///
+    /// Only the default column family can be opened, and no handle is kept for
+    /// it, so the `_cf` methods such as `delete_range_cf` cannot be used. Per
+    /// column family TTLs need `rocksdb_open_column_families_with_ttl`, which
+    /// the pinned librocksdb-sys (RocksDB 6.1) lacks.
+    pub fn open_with_ttl<P: AsRef<Path>>(opts: &Options, path: P, ttl: Duration) -> Result<DB, Error> {
+        if ttl.as_secs() == 0 && ttl.subsec_nanos() > 0 {
+            return Err(Error::with_kind(
+                ErrorKind::InvalidArgument,
+                format!("TTL must be zero or at least one second, got {:?}", ttl),
+            ));
+        }
+        DB::open_cf_descriptors_internal(opts, path, vec![], AccessType::WithTTL { ttl: ttl })
+    }
+
+    /// Open a database for reading only. Any number of read-only instances can
+    /// be opened alongside the process writing to the database, and see its
+    /// state as of the time they were opened.
+    ///
+    /// If `error_if_log_file_exist` is true, opening fails when there are
+    /// writes in the WAL that were not yet flushed.
+    ///
+    /// Writes to a read-only instance fail with an `ErrorKind::NotSupported` error.
+    pub fn open_for_read_only<P: AsRef<Path>>(
+        opts: &Options,
+        path: P,
+        error_if_log_file_exist: bool,
+    ) -> Result<DB, Error> {
+        DB::open_cf_for_read_only(opts, path, vec![], error_if_log_file_exist)
+    }
+
+    /// Open a database and some of its column families for reading only.
+    pub fn open_cf_for_read_only<P: AsRef<Path>>(
+        opts: &Options,
+        path: P,
+        cfs: Vec<ColumnFamilyDescriptor>,
+        error_if_log_file_exist: bool,
+    ) -> Result<DB, Error> {
+        let access_type = AccessType::ReadOnly { error_if_log_file_exist: error_if_log_file_exist };
+        DB::open_cf_descriptors_internal(opts, path, cfs, access_type)
+    }
+
+    fn open_cf_descriptors_internal<P: AsRef<Path>>(
+        opts: &Options,
+        path: P,
+        cfs: Vec<ColumnFamilyDescriptor>,
+        access_type: AccessType,
+    ) -> Result<DB, Error> {
         let path = path.as_ref();
         let cpath = match CString::new(path.to_string_lossy().as_bytes()) {
             Ok(c) => c,
@@ -71,70 +143,71 @@ impl DB {
             }
         };
 
-        if let Err(e) = fs::create_dir_all(&path) {
-            return Err(Error::new(format!(
-                "Failed to create RocksDB\
-                                           directory: `{:?}`.",
-                e
-            )));
-        }
-
-        let db: *mut ffi::rocksdb_t;
-        let mut cf_map = BTreeMap::new();
-
-        if cfs.len() == 0 {
-            unsafe {
-                db = ffi_try!(ffi::rocksdb_open(opts.inner, cpath.as_ptr() as *const _,));
-            }
-        } else {
-            let mut cfs_v = cfs;
-            // Always open the default column family.
-            if !cfs_v.iter().any(|cf| cf.name == "default") {
-                cfs_v.push(ColumnFamilyDescriptor {
-                    name: String::from("default"),
-                    options: Options::default()
-                });
-            }
-            // We need to store our CStrings in an intermediate vector
-            // so that their pointers remain valid.
-            let c_cfs: Vec<CString> = cfs_v
-                .iter()
-                .map(|cf| CString::new(cf.name.as_bytes()).unwrap())
-                .collect();
-
-            let mut cfnames: Vec<_> = c_cfs.iter().map(|cf| cf.as_ptr()).collect();
-
-            // These handles will be populated by DB.
-            let mut cfhandles: Vec<_> = cfs_v.iter().map(|_| ptr::null_mut()).collect();
-
-            let mut cfopts: Vec<_> = cfs_v.iter()
-                .map(|cf| cf.options.inner as *const _)
-                .collect();
-
-            unsafe {
-                db = ffi_try!(ffi::rocksdb_open_column_families(
-                    opts.inner,
-                    cpath.as_ptr(),
-                    cfs_v.len() as c_int,
-                    cfnames.as_mut_ptr(),
-                    cfopts.as_mut_ptr(),
-                    cfhandles.as_mut_ptr(),));
+        // A read-only instance needs an existing database.
+        let create_dir = match access_type {
+            AccessType::ReadOnly { .. } => false,
+            AccessType::ReadWrite | AccessType::WithTTL { .. } => true,
+        };
+        if create_dir {
+            if let Err(e) = fs::create_dir_all(&path) {
+                return Err(Error::new(format!(
+                    "Failed to create RocksDB\
+                                               directory: `{:?}`.",
+                    e
+                )));
             }
+        }
 
-            for handle in &cfhandles {
-                if handle.is_null() {
-                    return Err(Error::new(
-                        "Received null column family \
-                                           handle from DB."
-                            .to_owned(),
-                    ));
+        let (db, cf_map) = if cfs.len() == 0 {
+            let db: *mut ffi::rocksdb_t = unsafe {
+                match access_type {
+                    AccessType::ReadWrite => {
+                        ffi_try!(ffi::rocksdb_open(opts.inner, cpath.as_ptr() as *const _,))
+                    }
+                    AccessType::ReadOnly { error_if_log_file_exist } => {
+                        ffi_try!(ffi::rocksdb_open_for_read_only(
+                            opts.inner,
+                            cpath.as_ptr() as *const _,
+                            error_if_log_file_exist as c_uchar,
+                        ))
+                    }
+                    AccessType::WithTTL { ttl } => {
+                        let ttl = cmp::min(ttl.as_secs(), c_int::max_value() as u64) as c_int;
+                        ffi_try!(ffi::rocksdb_open_with_ttl(
+                            opts.inner,
+                            cpath.as_ptr() as *const _,
+                            ttl,
+                        ))
+                    }
                 }
-            }
-
-            for (n, h) in cfs_v.iter().zip(cfhandles) {
-                cf_map.insert(n.name.clone(), ColumnFamily { inner: h });
-            }
-        }
+            };
+            (db, BTreeMap::new())
+        } else {
+            try!(open_column_families(cfs, |n, names, cfopts, cfhandles| unsafe {
+                Ok(match access_type {
+                    AccessType::ReadWrite => ffi_try!(ffi::rocksdb_open_column_families(
+                        opts.inner,
+                        cpath.as_ptr(),
+                        n,
+                        names,
+                        cfopts,
+                        cfhandles,
+                    )),
+                    AccessType::ReadOnly { error_if_log_file_exist } => {
+                        ffi_try!(ffi::rocksdb_open_for_read_only_column_families(
+                            opts.inner,
+                            cpath.as_ptr(),
+                            n,
+                            names,
+                            cfopts,
+                            cfhandles,
+                            error_if_log_file_exist as c_uchar,
+                        ))
+                    }
+                    AccessType::WithTTL { .. } => unreachable!("open_with_ttl takes no column families"),
+                })
+            }))
+        };
 
         if db.is_null() {
             return Err(Error::new("Could not initialize database.".to_owned()));
@@ -288,6 +361,67 @@ impl DB {
         self.get_cf_opt(cf, key, &ReadOptions::default())
     }
 
+    /// Looks up several keys with a single call, returning one result per key in
+    /// the order of `keys`.
+    pub fn multi_get_opt<K: AsRef<[u8]>>(
+        &self,
+        keys: &[K],
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<DBVector>, Error>> {
+        let (keys, keys_sizes) = keys_to_ptrs(keys);
+        let mut values = vec![ptr::null_mut(); keys.len()];
+        let mut values_sizes = vec![0; keys.len()];
+        let mut errors = vec![ptr::null_mut(); keys.len()];
+        unsafe {
+            ffi::rocksdb_multi_get(
+                self.inner,
+                readopts.inner,
+                keys.len() as size_t,
+                keys.as_ptr(),
+                keys_sizes.as_ptr(),
+                values.as_mut_ptr(),
+                values_sizes.as_mut_ptr(),
+                errors.as_mut_ptr(),
+            );
+        }
+        multi_get_results(values, values_sizes, errors)
+    }
+
+    pub fn multi_get<K: AsRef<[u8]>>(&self, keys: &[K]) -> Vec<Result<Option<DBVector>, Error>> {
+        self.multi_get_opt(keys, &ReadOptions::default())
+    }
+
+    /// Looks up several keys, each in its own column family, with a single call.
+    pub fn multi_get_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        keys: &[(ColumnFamily, K)],
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<DBVector>, Error>> {
+        let cfs: Vec<_> = keys.iter().map(|&(ref cf, _)| cf.inner as *const _).collect();
+        let (keys, keys_sizes) = keys_to_ptrs(&keys.iter().map(|&(_, ref k)| k.as_ref()).collect::<Vec<_>>());
+        let mut values = vec![ptr::null_mut(); keys.len()];
+        let mut values_sizes = vec![0; keys.len()];
+        let mut errors = vec![ptr::null_mut(); keys.len()];
+        unsafe {
+            ffi::rocksdb_multi_get_cf(
+                self.inner,
+                readopts.inner,
+                cfs.as_ptr(),
+                keys.len() as size_t,
+                keys.as_ptr(),
+                keys_sizes.as_ptr(),
+                values.as_mut_ptr(),
+                values_sizes.as_mut_ptr(),
+                errors.as_mut_ptr(),
+            );
+        }
+        multi_get_results(values, values_sizes, errors)
+    }
+
+    pub fn multi_get_cf<K: AsRef<[u8]>>(&self, keys: &[(ColumnFamily, K)]) -> Vec<Result<Option<DBVector>, Error>> {
+        self.multi_get_cf_opt(keys, &ReadOptions::default())
+    }
+
     pub fn create_cf(&mut self, name: &str, opts: &Options) -> Result<ColumnFamily, Error> {
         let cname = match CString::new(name.as_bytes()) {
             Ok(c) => c,
@@ -333,6 +467,58 @@ impl DB {
         self.cfs.get(name).cloned()
     }
 
+    /// Returns the value of a string property, such as `Property::Stats`, or
+    /// `None` if the property is unknown.
+    pub fn property_value<P: AsRef<str>>(&self, name: P) -> Result<Option<String>, Error> {
+        let name = try!(property_name(name.as_ref()));
+        unsafe { Ok(property_value(ffi::rocksdb_property_value(self.inner, name.as_ptr()))) }
+    }
+
+    /// Returns the value of a string property for a column family, or `None` if
+    /// the property is unknown.
+    pub fn property_value_cf<P: AsRef<str>>(
+        &self,
+        cf: ColumnFamily,
+        name: P,
+    ) -> Result<Option<String>, Error> {
+        let name = try!(property_name(name.as_ref()));
+        unsafe {
+            Ok(property_value(ffi::rocksdb_property_value_cf(self.inner, cf.inner, name.as_ptr())))
+        }
+    }
+
+    /// Returns the value of an integer property, such as `Property::EstimateNumKeys`,
+    /// or `None` if the property is unknown or not an integer.
+    pub fn property_int_value<P: AsRef<str>>(&self, name: P) -> Result<Option<u64>, Error> {
+        let name = try!(property_name(name.as_ref()));
+        let mut value: u64 = 0;
+        unsafe {
+            if ffi::rocksdb_property_int(self.inner, name.as_ptr(), &mut value) == 0 {
+                Ok(Some(value))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the value of an integer property for a column family, or `None`
+    /// if the property is unknown or not an integer.
+    pub fn property_int_value_cf<P: AsRef<str>>(
+        &self,
+        cf: ColumnFamily,
+        name: P,
+    ) -> Result<Option<u64>, Error> {
+        let name = try!(property_name(name.as_ref()));
+        let mut value: u64 = 0;
+        unsafe {
+            if ffi::rocksdb_property_int_cf(self.inner, cf.inner, name.as_ptr(), &mut value) == 0 {
+                Ok(Some(value))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
     pub fn iterator(&self, mode: IteratorMode) -> DBIterator {
         let opts = ReadOptions::default();
         DBIterator::new(self, &opts, mode)
@@ -353,6 +539,47 @@ impl DB {
         DBIterator::new(self, &opts, IteratorMode::From(prefix, Direction::Forward))
     }
 
+    /// Opens an iterator over the keys in `range`, using the iterate bounds of
+    /// `ReadOptions` so that RocksDB itself stops at either end.
+    ///
+    /// The iterator starts at the beginning of the range; use
+    /// `set_mode(IteratorMode::End)` to walk it backwards from its end.
+    /// Keys are assumed to be ordered bytewise.
+    ///
+    /// ```
+    /// use rocksdb::{DB, IteratorMode, Options};
+    ///
+    /// let path = "_rust_rocksdb_range_iterator_doctest";
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     for (key, value) in db.range_iterator(&b"a"[..]..&b"c"[..]) {
+    ///         println!("Saw {:?} {:?}", key, value);
+    ///     }
+    ///     let mut iter = db.range_iterator(&b"a"[..]..=&b"c"[..]);
+    ///     iter.set_mode(IteratorMode::End);
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn range_iterator<'a, R>(&self, range: R) -> DBIterator
+    where
+        R: RangeBounds<&'a [u8]>,
+    {
+        let mut opts = ReadOptions::default();
+        // both RocksDB bounds are for [lower, upper), so an included upper and an
+        // excluded lower key move to their immediate successor, `key\0`
+        match range.start_bound() {
+            Bound::Included(key) => opts.set_iterate_lower_bound(key),
+            Bound::Excluded(key) => opts.set_iterate_lower_bound(&[*key, &[0u8][..]].concat()),
+            Bound::Unbounded => {}
+        }
+        match range.end_bound() {
+            Bound::Included(key) => opts.set_iterate_upper_bound(&[*key, &[0u8][..]].concat()),
+            Bound::Excluded(key) => opts.set_iterate_upper_bound(key),
+            Bound::Unbounded => {}
+        }
+        DBIterator::from_raw(DBRawIterator::with_readopts(self, opts), IteratorMode::Start)
+    }
+
     pub fn iterator_cf(
         &self,
         cf_handle: ColumnFamily,
@@ -501,6 +728,29 @@ impl DB {
         }
     }
 
+    /// Removes the database entries in the range `["from", "to")` of the given
+    /// column family by writing a single range tombstone.
+    pub fn delete_range_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        from: &[u8],
+        to: &[u8],
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_delete_range_cf(
+                self.inner,
+                writeopts.inner,
+                cf.inner,
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
     pub fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
         self.put_opt(key, value, &WriteOptions::default())
     }
@@ -525,6 +775,113 @@ impl DB {
         self.delete_cf_opt(cf, key, &WriteOptions::default())
     }
 
+    pub fn delete_range_cf(&self, cf: ColumnFamily, from: &[u8], to: &[u8]) -> Result<(), Error> {
+        self.delete_range_cf_opt(cf, from, to, &WriteOptions::default())
+    }
+
+    /// Flushes the memtables to SST files, waiting for it to finish if
+    /// `FlushOptions::set_wait` is set.
+    pub fn flush_opt(&self, flushopts: &FlushOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_flush(self.inner, flushopts.inner,));
+            Ok(())
+        }
+    }
+
+    /// Flushes the memtables to SST files and waits for it to finish.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.flush_opt(&FlushOptions::default())
+    }
+
+    /// Flushes the memtables of a column family to SST files.
+    pub fn flush_cf_opt(&self, cf: ColumnFamily, flushopts: &FlushOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_flush_cf(self.inner, flushopts.inner, cf.inner,));
+            Ok(())
+        }
+    }
+
+    /// Flushes the memtables of a column family to SST files and waits for it to finish.
+    pub fn flush_cf(&self, cf: ColumnFamily) -> Result<(), Error> {
+        self.flush_cf_opt(cf, &FlushOptions::default())
+    }
+
+    /// Adds SST files built with `SstFileWriter` to the database.
+    pub fn ingest_external_file_opt<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        ingestopts: &IngestExternalFileOptions,
+    ) -> Result<(), Error> {
+        let cpaths = try!(paths_to_cstrings(paths));
+        let ptrs: Vec<*const c_char> = cpaths.iter().map(|p| p.as_ptr()).collect();
+        unsafe {
+            ffi_try!(ffi::rocksdb_ingest_external_file(
+                self.inner,
+                ptrs.as_ptr(),
+                ptrs.len() as size_t,
+                ingestopts.inner,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn ingest_external_file<P: AsRef<Path>>(&self, paths: &[P]) -> Result<(), Error> {
+        self.ingest_external_file_opt(paths, &IngestExternalFileOptions::default())
+    }
+
+    /// Adds SST files built with `SstFileWriter` to a column family.
+    pub fn ingest_external_file_cf_opt<P: AsRef<Path>>(
+        &self,
+        cf: ColumnFamily,
+        paths: &[P],
+        ingestopts: &IngestExternalFileOptions,
+    ) -> Result<(), Error> {
+        let cpaths = try!(paths_to_cstrings(paths));
+        let ptrs: Vec<*const c_char> = cpaths.iter().map(|p| p.as_ptr()).collect();
+        unsafe {
+            ffi_try!(ffi::rocksdb_ingest_external_file_cf(
+                self.inner,
+                cf.inner,
+                ptrs.as_ptr(),
+                ptrs.len() as size_t,
+                ingestopts.inner,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn ingest_external_file_cf<P: AsRef<Path>>(
+        &self,
+        cf: ColumnFamily,
+        paths: &[P],
+    ) -> Result<(), Error> {
+        self.ingest_external_file_cf_opt(cf, paths, &IngestExternalFileOptions::default())
+    }
+
+    /// Compacts the keys between `start` and `end`, or the whole key space when
+    /// both are `None`.
+    ///
+    /// The compaction functions of the RocksDB 6.1 C API discard the status of
+    /// the compaction, so a failed compaction cannot be reported and the
+    /// `compact_range` methods return nothing.
+    pub fn compact_range_opt(
+        &self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        compactopts: &CompactRangeOptions,
+    ) {
+        unsafe {
+            ffi::rocksdb_compact_range_opt(
+                self.inner,
+                compactopts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
+
     pub fn compact_range(&self, start: Option<&[u8]>, end: Option<&[u8]>) {
         unsafe {
             ffi::rocksdb_compact_range(
@@ -537,6 +894,28 @@ impl DB {
         }
     }
 
+    /// Compacts the keys of a column family between `start` and `end`, or the
+    /// whole column family when both are `None`.
+    pub fn compact_range_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        compactopts: &CompactRangeOptions,
+    ) {
+        unsafe {
+            ffi::rocksdb_compact_range_cf_opt(
+                self.inner,
+                cf.inner,
+                compactopts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
+
     pub fn compact_range_cf(&self, cf: ColumnFamily, start: Option<&[u8]>, end: Option<&[u8]>) {
         unsafe {
             ffi::rocksdb_compact_range_cf(
@@ -602,6 +981,35 @@ fn errors_do_stuff() {
     }
 }
 
+#[test]
+fn error_kind_test() {
+    let path = "_rust_rocksdb_error_kind";
+    {
+        let _db = DB::open_default(path).unwrap();
+        // the lock held by the open DB makes destroy fail with an IO error
+        let err = DB::destroy(&Options::default(), path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IOError);
+        assert!(!err.is_retryable());
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+fn multi_get_test() {
+    let path = "_rust_rocksdb_multigettest";
+    {
+        let db = DB::open_default(path).unwrap();
+        assert!(db.put(b"k1", b"v1111").is_ok());
+        assert!(db.put(b"k3", b"v3333").is_ok());
+        let values = db.multi_get(&[b"k1", b"k2", b"k3"]);
+        assert_eq!(values.len(), 3);
+        assert!(values[0].as_ref().unwrap().as_ref().unwrap().to_utf8().unwrap() == "v1111");
+        assert!(values[1].as_ref().unwrap().is_none());
+        assert!(values[2].as_ref().unwrap().as_ref().unwrap().to_utf8().unwrap() == "v3333");
+    }
+    let opts = Options::default();
+    assert!(DB::destroy(&opts, path).is_ok());
+}
 
 #[test]
 fn iterator_test() {
@@ -21,7 +21,9 @@ use libc::{self, c_int, c_uchar, c_uint, c_void, size_t, uint64_t};
 use ffi;
 use {BlockBasedOptions, DBCompactionStyle, DBCompressionType, DBRecoveryMode, MemtableFactory,
      Options};
-use compaction_filter::{self, CompactionFilterCallback, CompactionFilterFn, filter_callback};
+use compaction_filter::{self, CompactionFilterCallback, CompactionFilterFactory,
+                        CompactionFilterFactoryCallback, CompactionFilterFn,
+                        create_filter_callback};
 use comparator::{self, ComparatorCallback, CompareFn};
 use merge_operator::{self, MergeFn, MergeOperatorCallback, full_merge_callback,
                      partial_merge_callback};
@@ -172,24 +174,31 @@ impl Options {
         }
     }
 
-    pub fn set_merge_operator(&mut self, name: &str,
-                              full_merge_fn: MergeFn,
-                              partial_merge_fn: Option<MergeFn>) {
+    /// Sets a merge operator. `full_merge_fn` is used for partial merges too
+    /// when `partial_merge_fn` is `None`, which is correct for associative
+    /// operators.
+    ///
+    /// Both may be plain functions or closures capturing configuration or shared
+    /// state. They share a type, so two different functions must be cast to
+    /// `merge_operator::MergeFnPtr`.
+    pub fn set_merge_operator<F: MergeFn>(&mut self, name: &str,
+                                          full_merge_fn: F,
+                                          partial_merge_fn: Option<F>) {
 
         let cb = Box::new(MergeOperatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             full_merge_fn: full_merge_fn,
-            partial_merge_fn: partial_merge_fn.unwrap_or(full_merge_fn),
+            partial_merge_fn: partial_merge_fn,
         });
 
         unsafe {
             let mo = ffi::rocksdb_mergeoperator_create(
                 mem::transmute(cb),
-                Some(merge_operator::destructor_callback),
-                Some(full_merge_callback),
-                Some(partial_merge_callback),
+                Some(merge_operator::destructor_callback::<F>),
+                Some(full_merge_callback::<F>),
+                Some(partial_merge_callback::<F>),
                 None,
-                Some(merge_operator::name_callback),
+                Some(merge_operator::name_callback::<F>),
             );
             ffi::rocksdb_options_set_merge_operator(self.inner, mo);
         }
@@ -197,7 +206,7 @@ impl Options {
 
     #[deprecated(since = "0.5.0",
                  note = "add_merge_operator has been renamed to set_merge_operator")]
-    pub fn add_merge_operator(&mut self, name: &str, merge_fn: MergeFn) {
+    pub fn add_merge_operator<F: MergeFn>(&mut self, name: &str, merge_fn: F) {
         self.set_merge_operator(name, merge_fn, None);
     }
 
@@ -209,11 +218,14 @@ impl Options {
     /// If you take a snapshot of the database, only values written since the last
     /// snapshot will be passed through the compaction filter.
     ///
+    /// `filter_fn` is told whether the compaction was requested manually. Values
+    /// returned through `Decision::Change` are owned and may be computed on the fly.
+    ///
     /// If multi-threaded compaction is used, `filter_fn` may be called multiple times
-    /// simultaneously.
+    /// simultaneously, so it only gets shared access to its captured state.
     pub fn set_compaction_filter<F>(&mut self, name: &str, filter_fn: F)
     where
-        F: CompactionFilterFn + Send + 'static,
+        F: CompactionFilterFn,
     {
         let cb = Box::new(CompactionFilterCallback {
             name: CString::new(name.as_bytes()).unwrap(),
@@ -221,13 +233,74 @@ impl Options {
         });
 
         unsafe {
-            let cf = ffi::rocksdb_compactionfilter_create(
+            // A new filter is built for every compaction so that it can be told
+            // whether the compaction is manual.
+            let factory = ffi::rocksdb_compactionfilterfactory_create(
                 mem::transmute(cb),
                 Some(compaction_filter::destructor_callback::<F>),
-                Some(filter_callback::<F>),
+                Some(create_filter_callback::<F>),
                 Some(compaction_filter::name_callback::<F>),
             );
-            ffi::rocksdb_options_set_compaction_filter(self.inner, cf);
+            ffi::rocksdb_options_set_compaction_filter_factory(self.inner, factory);
+        }
+    }
+
+    /// Sets a factory that builds a new compaction filter for every compaction job.
+    ///
+    /// Unlike `set_compaction_filter`, each filter is told about the compaction it
+    /// runs in and can keep its own state, since it is only used by that job.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rocksdb::{CompactionDecision, Options};
+    /// use rocksdb::compaction_filter::{CompactionFilter, CompactionFilterContext,
+    ///                                  CompactionFilterFactory};
+    ///
+    /// struct DedupFilter {
+    ///     last_value: Option<Vec<u8>>,
+    /// }
+    ///
+    /// impl CompactionFilter for DedupFilter {
+    ///     fn filter(&mut self, _level: u32, _key: &[u8], value: &[u8]) -> CompactionDecision {
+    ///         if self.last_value.as_ref().map_or(false, |v| &v[..] == value) {
+    ///             return CompactionDecision::Remove;
+    ///         }
+    ///         self.last_value = Some(value.to_vec());
+    ///         CompactionDecision::Keep
+    ///     }
+    /// }
+    ///
+    /// struct DedupFactory;
+    ///
+    /// impl CompactionFilterFactory for DedupFactory {
+    ///     type Filter = DedupFilter;
+    ///
+    ///     fn create(&self, _context: CompactionFilterContext) -> DedupFilter {
+    ///         DedupFilter { last_value: None }
+    ///     }
+    /// }
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_compaction_filter_factory("dedup", DedupFactory);
+    /// ```
+    pub fn set_compaction_filter_factory<T>(&mut self, name: &str, factory: T)
+    where
+        T: CompactionFilterFactory,
+    {
+        let cb = Box::new(CompactionFilterFactoryCallback {
+            name: CString::new(name.as_bytes()).unwrap(),
+            factory: factory,
+        });
+
+        unsafe {
+            let factory = ffi::rocksdb_compactionfilterfactory_create(
+                mem::transmute(cb),
+                Some(compaction_filter::factory_destructor_callback::<T>),
+                Some(compaction_filter::factory_create_callback::<T>),
+                Some(compaction_filter::factory_name_callback::<T>),
+            );
+            ffi::rocksdb_options_set_compaction_filter_factory(self.inner, factory);
         }
     }
 
@@ -237,7 +310,24 @@ impl Options {
     /// The client must ensure that the comparator supplied here has the same
     /// name and orders keys *exactly* the same as the comparator provided to
     /// previous open calls on the same DB.
-    pub fn set_comparator(&mut self, name: &str, compare_fn: CompareFn) {
+    ///
+    /// `compare_fn` may be any closure, so it can capture the state it needs to
+    /// order keys. Ready-made comparators live in the `comparator` module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rocksdb::Options;
+    /// use rocksdb::comparator;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_comparator("reverse", comparator::reverse_bytewise);
+    ///
+    /// // sort keys case-insensitively
+    /// let fold = |key: &[u8]| key.to_ascii_lowercase();
+    /// opts.set_comparator("case_insensitive", move |a: &[u8], b: &[u8]| fold(a).cmp(&fold(b)));
+    /// ```
+    pub fn set_comparator<F: CompareFn>(&mut self, name: &str, compare_fn: F) {
         let cb = Box::new(ComparatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             f: compare_fn,
@@ -246,9 +336,9 @@ impl Options {
         unsafe {
             let cmp = ffi::rocksdb_comparator_create(
                 mem::transmute(cb),
-                Some(comparator::destructor_callback),
-                Some(comparator::compare_callback),
-                Some(comparator::name_callback),
+                Some(comparator::destructor_callback::<F>),
+                Some(comparator::compare_callback::<F>),
+                Some(comparator::name_callback::<F>),
             );
             ffi::rocksdb_options_set_comparator(self.inner, cmp);
         }
@@ -263,7 +353,7 @@ impl Options {
     }
 
     #[deprecated(since = "0.5.0", note = "add_comparator has been renamed to set_comparator")]
-    pub fn add_comparator(&mut self, name: &str, compare_fn: CompareFn) {
+    pub fn add_comparator<F: CompareFn>(&mut self, name: &str, compare_fn: F) {
         self.set_comparator(name, compare_fn);
     }
 
@@ -923,6 +1013,12 @@ impl Options {
         }
     }
 
+    /// Returns the statistics collected since `enable_statistics` as text, or
+    /// `None` if statistics are not enabled.
+    ///
+    /// The text format is not stable. Typed tickers and histograms need
+    /// `rocksdb_options_statistics_get_ticker_count` and its histogram
+    /// counterpart, which the pinned librocksdb-sys (RocksDB 6.1) lacks.
     pub fn get_statistics(&self) -> Option<String> {
         unsafe {
             let value = ffi::rocksdb_options_statistics_get_string(self.inner);
@@ -1026,6 +1122,17 @@ impl Options {
         }
     }
 
+    /// Reserves the bottommost level for files ingested with
+    /// `IngestExternalFileOptions::set_ingest_behind`. Only works with
+    /// universal compaction.
+    ///
+    /// Default: false
+    pub fn set_allow_ingest_behind(&mut self, allow: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_allow_ingest_behind(self.inner, allow as c_uchar);
+        }
+    }
+
     /// Specify the maximal number of info log files to be kept.
     pub fn set_keep_log_file_num(&mut self, nfiles: usize) {
         unsafe {
@@ -13,8 +13,13 @@
 // limitations under the License.
 //
 
-use libc::{self, c_char, c_void};
-use std::ffi::CStr;
+use {ColumnFamily, ColumnFamilyDescriptor, DBVector, Error, ErrorKind, Options};
+use ffi;
+
+use libc::{self, c_char, c_int, c_void, size_t};
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+use std::path::Path;
 use std::ptr;
 
 pub fn error_message(ptr: *const c_char) -> String {
@@ -26,6 +31,134 @@ pub fn error_message(ptr: *const c_char) -> String {
     s
 }
 
+/// Converts the paths of a file list, such as the one taken by
+/// `rocksdb_ingest_external_file`.
+pub fn paths_to_cstrings<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<CString>, Error> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+            CString::new(path.to_string_lossy().as_bytes()).map_err(|_| {
+                Error::new(format!("Failed to convert path {:?} to CString", path))
+            })
+        })
+        .collect()
+}
+
+/// Converts a property name for the `rocksdb_property_*` functions.
+pub fn property_name(name: &str) -> Result<CString, Error> {
+    match CString::new(name) {
+        Ok(c) => Ok(c),
+        Err(_) => Err(Error::new(format!(
+            "Failed to convert property name {:?} to CString",
+            name
+        ))),
+    }
+}
+
+/// Takes ownership of a string returned by `rocksdb_property_value`, which is
+/// null for unknown properties and otherwise allocated with `malloc`.
+pub fn property_value(ptr: *mut c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    let value = unsafe { CStr::from_ptr(ptr as *const _) }.to_string_lossy().into_owned();
+    unsafe {
+        libc::free(ptr as *mut c_void);
+    }
+    Some(value)
+}
+
+/// Opens a database with the given column families, plus the default one,
+/// which is always opened. `open` makes the `*_open_column_families` call with
+/// the number of column families, their names and options, and the array to
+/// write their handles to.
+pub fn open_column_families<T, F>(
+    cfs: Vec<ColumnFamilyDescriptor>,
+    open: F,
+) -> Result<(*mut T, BTreeMap<String, ColumnFamily>), Error>
+where
+    F: FnOnce(
+        c_int,
+        *mut *const c_char,
+        *mut *const ffi::rocksdb_options_t,
+        *mut *mut ffi::rocksdb_column_family_handle_t,
+    ) -> Result<*mut T, Error>,
+{
+    let mut cfs_v = cfs;
+    // Always open the default column family.
+    if !cfs_v.iter().any(|cf| cf.name == "default") {
+        cfs_v.push(ColumnFamilyDescriptor {
+            name: String::from("default"),
+            options: Options::default()
+        });
+    }
+    // We need to store our CStrings in an intermediate vector
+    // so that their pointers remain valid.
+    let c_cfs: Vec<CString> = cfs_v
+        .iter()
+        .map(|cf| CString::new(cf.name.as_bytes()).unwrap())
+        .collect();
+
+    let mut cfnames: Vec<_> = c_cfs.iter().map(|cf| cf.as_ptr()).collect();
+
+    // These handles will be populated by DB.
+    let mut cfhandles: Vec<_> = cfs_v.iter().map(|_| ptr::null_mut()).collect();
+
+    let mut cfopts: Vec<_> = cfs_v.iter()
+        .map(|cf| cf.options.inner as *const _)
+        .collect();
+
+    let db = try!(open(
+        cfs_v.len() as c_int,
+        cfnames.as_mut_ptr(),
+        cfopts.as_mut_ptr(),
+        cfhandles.as_mut_ptr(),
+    ));
+
+    for handle in &cfhandles {
+        if handle.is_null() {
+            return Err(Error::new(
+                "Received null column family \
+                                   handle from DB."
+                    .to_owned(),
+            ));
+        }
+    }
+
+    let mut cf_map = BTreeMap::new();
+    for (n, h) in cfs_v.iter().zip(cfhandles) {
+        cf_map.insert(n.name.clone(), ColumnFamily { inner: h });
+    }
+    Ok((db, cf_map))
+}
+
+/// Maps the prefix RocksDB puts on a status message (see `Status::ToString`)
+/// to an `ErrorKind`.
+pub fn error_kind(message: &str) -> ErrorKind {
+    const PREFIXES: &[(&str, ErrorKind)] = &[
+        ("Resource busy: Deadlock", ErrorKind::Deadlock),
+        ("NotFound: ", ErrorKind::NotFound),
+        ("Corruption: ", ErrorKind::Corruption),
+        ("Not implemented: ", ErrorKind::NotSupported),
+        ("Invalid argument: ", ErrorKind::InvalidArgument),
+        ("IO error: ", ErrorKind::IOError),
+        ("Merge in progress: ", ErrorKind::MergeInProgress),
+        ("Result incomplete: ", ErrorKind::Incomplete),
+        ("Shutdown in progress: ", ErrorKind::ShutdownInProgress),
+        ("Operation timed out: ", ErrorKind::TimedOut),
+        ("Operation aborted: ", ErrorKind::Aborted),
+        ("Resource busy: ", ErrorKind::Busy),
+        ("Operation expired: ", ErrorKind::Expired),
+        ("Operation failed. Try again.: ", ErrorKind::TryAgain),
+    ];
+
+    PREFIXES
+        .iter()
+        .find(|&&(prefix, _)| message.starts_with(prefix))
+        .map_or(ErrorKind::Other, |&(_, kind)| kind)
+}
+
 pub fn opt_bytes_to_ptr(opt: Option<&[u8]>) -> *const c_char {
     match opt {
         Some(v) => v.as_ptr() as *const c_char,
@@ -33,6 +166,35 @@ pub fn opt_bytes_to_ptr(opt: Option<&[u8]>) -> *const c_char {
     }
 }
 
+/// Splits keys into the pointer and length arrays taken by the `multi_get` family.
+pub fn keys_to_ptrs<K: AsRef<[u8]>>(keys: &[K]) -> (Vec<*const c_char>, Vec<size_t>) {
+    keys.iter()
+        .map(|k| (k.as_ref().as_ptr() as *const c_char, k.as_ref().len() as size_t))
+        .unzip()
+}
+
+/// Converts the output arrays of a `multi_get` call into one result per key.
+pub fn multi_get_results(
+    values: Vec<*mut c_char>,
+    values_sizes: Vec<size_t>,
+    errors: Vec<*mut c_char>,
+) -> Vec<Result<Option<DBVector>, Error>> {
+    values
+        .into_iter()
+        .zip(values_sizes.into_iter())
+        .zip(errors.into_iter())
+        .map(|((v, s), e)| {
+            if !e.is_null() {
+                Err(Error::new(error_message(e)))
+            } else if v.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(unsafe { DBVector::from_c(v as *mut u8, s) }))
+            }
+        })
+        .collect()
+}
+
 macro_rules! ffi_try {
     ( $($function:ident)::*( $( $arg:expr,)* ) ) => ({
         let mut err: *mut ::libc::c_char = ::std::ptr::null_mut();
@@ -43,3 +205,17 @@ macro_rules! ffi_try {
         result
     })
 }
+
+#[test]
+fn test_error_kind() {
+    assert_eq!(error_kind("Resource busy: "), ErrorKind::Busy);
+    assert_eq!(error_kind("Resource busy: Deadlock"), ErrorKind::Deadlock);
+    assert_eq!(error_kind("Operation timed out: Timeout waiting to lock key"), ErrorKind::TimedOut);
+    assert_eq!(error_kind("IO error: lock _rust_rocksdb_error/LOCK: No locks available"), ErrorKind::IOError);
+    assert_eq!(error_kind("Result incomplete: Write stall"), ErrorKind::Incomplete);
+    assert_eq!(error_kind("Invalid column family: cf1"), ErrorKind::Other);
+
+    assert!(Error::new("Result incomplete: Write stall".to_owned()).is_write_stall());
+    assert!(Error::new("Result incomplete: Low priority write stall".to_owned()).is_write_stall());
+    assert!(!Error::new("Result incomplete: ".to_owned()).is_write_stall());
+}
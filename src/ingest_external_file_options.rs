@@ -0,0 +1,90 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use {IngestExternalFileOptions};
+use libc::c_uchar;
+
+impl Drop for IngestExternalFileOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_destroy(self.inner);
+        }
+    }
+}
+
+impl IngestExternalFileOptions {
+    pub fn new() -> IngestExternalFileOptions {
+        IngestExternalFileOptions::default()
+    }
+
+    /// If true, the files are moved into the database instead of copied.
+    ///
+    /// Default: false
+    pub fn set_move_files(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_set_move_files(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If true, snapshots taken before the ingestion don't see the ingested keys.
+    ///
+    /// Default: true
+    pub fn set_snapshot_consistency(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_set_snapshot_consistency(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If false, ingesting files whose keys overlap existing keys fails instead
+    /// of assigning them a global sequence number.
+    ///
+    /// Default: true
+    pub fn set_allow_global_seqno(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_set_allow_global_seqno(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If false, ingesting files that overlap the memtable fails instead of
+    /// flushing it first.
+    ///
+    /// Default: true
+    pub fn set_allow_blocking_flush(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_set_allow_blocking_flush(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If true, the files are added to the bottommost level, behind all existing
+    /// data, so existing keys take precedence. Requires the database to be
+    /// opened with `allow_ingest_behind`.
+    ///
+    /// Default: false
+    pub fn set_ingest_behind(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_ingestexternalfileoptions_set_ingest_behind(self.inner, v as c_uchar);
+        }
+    }
+}
+
+impl Default for IngestExternalFileOptions {
+    fn default() -> IngestExternalFileOptions {
+        let ingest_opts = unsafe { ffi::rocksdb_ingestexternalfileoptions_create() };
+        if ingest_opts.is_null() {
+            panic!("Could not create RocksDB ingest external file options");
+        }
+        IngestExternalFileOptions { inner: ingest_opts }
+    }
+}
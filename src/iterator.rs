@@ -49,7 +49,12 @@ unsafe impl Send for DBIterator {}
 /// ```
 impl DBRawIterator {
     pub fn new(db: &DB, readopts: &ReadOptions) -> DBRawIterator {
-        unsafe { DBRawIterator { inner: ffi::rocksdb_create_iterator(db.inner, readopts.inner) } }
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_create_iterator(db.inner, readopts.inner),
+                _readopts: None,
+            }
+        }
     }
 
     pub fn new_cf(
@@ -60,10 +65,22 @@ impl DBRawIterator {
         unsafe {
             Ok(DBRawIterator {
                 inner: ffi::rocksdb_create_iterator_cf(db.inner, readopts.inner, cf_handle.inner),
+                _readopts: None,
             })
         }
     }
 
+    /// Creates an iterator that keeps `readopts`, and the iterate bounds stored
+    /// in them, alive for as long as it exists.
+    pub(crate) fn with_readopts(db: &DB, readopts: ReadOptions) -> DBRawIterator {
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_create_iterator(db.inner, readopts.inner),
+                _readopts: Some(readopts),
+            }
+        }
+    }
+
     /// Returns true if the iterator is valid.
     pub fn valid(&self) -> bool {
         unsafe { ffi::rocksdb_iter_valid(self.inner) != 0 }
@@ -272,6 +289,32 @@ impl DBRawIterator {
     pub fn value(&self) -> Option<Vec<u8>> {
         unsafe { self.value_inner().map(|value| value.to_vec()) }
     }
+
+    /// Returns the current key and value without copying them.
+    ///
+    /// The slices borrow the iterator, so it can't be moved until they are dropped.
+    pub fn item(&self) -> Option<(&[u8], &[u8])> {
+        unsafe {
+            match (self.key_inner(), self.value_inner()) {
+                (Some(key), Some(value)) => Some((key, value)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Calls `f` with every key and value from the current position onwards,
+    /// without copying them. Stops early when `f` returns `false`.
+    pub fn for_each<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        while let Some(keep_going) = self.item().map(|(key, value)| f(key, value)) {
+            if !keep_going {
+                break;
+            }
+            self.next();
+        }
+    }
 }
 
 impl Drop for DBRawIterator {
@@ -308,6 +351,16 @@ impl DBIterator {
         Ok(rv)
     }
 
+    pub(crate) fn from_raw(raw: DBRawIterator, mode: IteratorMode) -> DBIterator {
+        let mut rv = DBIterator {
+            raw: raw,
+            direction: Direction::Forward, // blown away by set_mode()
+            just_seeked: false,
+        };
+        rv.set_mode(mode);
+        rv
+    }
+
     pub fn set_mode(&mut self, mode: IteratorMode) {
         match mode {
             IteratorMode::Start => {
@@ -1,8 +1,9 @@
-use {TransactionDBRawIterator, TransactionDB, ReadOptions, TransactionDBIterator, KVBytes, IteratorMode, Direction};
+use {TransactionDBRawIterator, TransactionDB, Transaction, ReadOptions, TransactionDBIterator, KVBytes, IteratorMode, Error, Direction, ColumnFamily};
 use libc::{c_char, size_t, c_uchar};
+use std::marker::PhantomData;
 use std::slice;
 
-unsafe impl Send for TransactionDBIterator {}
+unsafe impl<'a> Send for TransactionDBIterator<'a> {}
 /// An iterator over a database or column family, with specifiable
 /// ranges and direction.
 ///
@@ -47,9 +48,51 @@ unsafe impl Send for TransactionDBIterator {}
 ///     iter.prev();
 /// }
 /// ```
-impl TransactionDBRawIterator {
-    pub fn new(db: &TransactionDB, readopts: &ReadOptions) -> TransactionDBRawIterator {
-        unsafe { TransactionDBRawIterator { inner: ffi::rocksdb_transactiondb_create_iterator(db.inner, readopts.inner) } }
+impl<'a> TransactionDBRawIterator<'a> {
+    pub fn new(db: &'a TransactionDB, readopts: &ReadOptions) -> TransactionDBRawIterator<'a> {
+        unsafe {
+            TransactionDBRawIterator {
+                inner: ffi::rocksdb_transactiondb_create_iterator(db.inner, readopts.inner),
+                source: PhantomData,
+            }
+        }
+    }
+
+    pub fn new_cf(
+        db: &'a TransactionDB,
+        cf_handle: ColumnFamily,
+        readopts: &ReadOptions,
+    ) -> Result<TransactionDBRawIterator<'a>, Error> {
+        unsafe {
+            Ok(TransactionDBRawIterator {
+                inner: ffi::rocksdb_transactiondb_create_iterator_cf(db.inner, readopts.inner, cf_handle.inner),
+                source: PhantomData,
+            })
+        }
+    }
+
+    /// Iterates over the database as seen by `txn`, including its
+    /// uncommitted writes.
+    pub fn from_transaction(txn: &'a Transaction, readopts: &ReadOptions) -> TransactionDBRawIterator<'a> {
+        unsafe {
+            TransactionDBRawIterator {
+                inner: ffi::rocksdb_transaction_create_iterator(txn.inner, readopts.inner),
+                source: PhantomData,
+            }
+        }
+    }
+
+    pub fn from_transaction_cf(
+        txn: &'a Transaction,
+        cf_handle: ColumnFamily,
+        readopts: &ReadOptions,
+    ) -> TransactionDBRawIterator<'a> {
+        unsafe {
+            TransactionDBRawIterator {
+                inner: ffi::rocksdb_transaction_create_iterator_cf(txn.inner, readopts.inner, cf_handle.inner),
+                source: PhantomData,
+            }
+        }
     }
 
     /// Returns true if the iterator is valid.
@@ -260,9 +303,35 @@ impl TransactionDBRawIterator {
     pub fn value(&self) -> Option<Vec<u8>> {
         unsafe { self.value_inner().map(|value| value.to_vec()) }
     }
+
+    /// Returns the current key and value without copying them.
+    ///
+    /// The slices borrow the iterator, so it can't be moved until they are dropped.
+    pub fn item(&self) -> Option<(&[u8], &[u8])> {
+        unsafe {
+            match (self.key_inner(), self.value_inner()) {
+                (Some(key), Some(value)) => Some((key, value)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Calls `f` with every key and value from the current position onwards,
+    /// without copying them. Stops early when `f` returns `false`.
+    pub fn for_each<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        while let Some(keep_going) = self.item().map(|(key, value)| f(key, value)) {
+            if !keep_going {
+                break;
+            }
+            self.next();
+        }
+    }
 }
 
-impl Drop for TransactionDBRawIterator {
+impl<'a> Drop for TransactionDBRawIterator<'a> {
     fn drop(&mut self) {
         unsafe {
             ffi::rocksdb_iter_destroy(self.inner);
@@ -270,8 +339,8 @@ impl Drop for TransactionDBRawIterator {
     }
 }
 
-impl TransactionDBIterator {
-    pub fn new(db: &TransactionDB, readopts: &ReadOptions, mode: IteratorMode) -> TransactionDBIterator {
+impl<'a> TransactionDBIterator<'a> {
+    pub fn new(db: &'a TransactionDB, readopts: &ReadOptions, mode: IteratorMode) -> TransactionDBIterator<'a> {
         let mut rv = TransactionDBIterator {
             raw: TransactionDBRawIterator::new(db, readopts),
             direction: Direction::Forward, // blown away by set_mode()
@@ -281,6 +350,46 @@ impl TransactionDBIterator {
         rv
     }
 
+    pub fn new_cf(
+        db: &'a TransactionDB,
+        cf_handle: ColumnFamily,
+        readopts: &ReadOptions,
+        mode: IteratorMode,
+    ) -> Result<TransactionDBIterator<'a>, Error> {
+        let mut rv = TransactionDBIterator {
+            raw: try!(TransactionDBRawIterator::new_cf(db, cf_handle, readopts)),
+            direction: Direction::Forward, // blown away by set_mode()
+            just_seeked: false,
+        };
+        rv.set_mode(mode);
+        Ok(rv)
+    }
+
+    pub fn from_transaction(txn: &'a Transaction, readopts: &ReadOptions, mode: IteratorMode) -> TransactionDBIterator<'a> {
+        let mut rv = TransactionDBIterator {
+            raw: TransactionDBRawIterator::from_transaction(txn, readopts),
+            direction: Direction::Forward, // blown away by set_mode()
+            just_seeked: false,
+        };
+        rv.set_mode(mode);
+        rv
+    }
+
+    pub fn from_transaction_cf(
+        txn: &'a Transaction,
+        cf_handle: ColumnFamily,
+        readopts: &ReadOptions,
+        mode: IteratorMode,
+    ) -> TransactionDBIterator<'a> {
+        let mut rv = TransactionDBIterator {
+            raw: TransactionDBRawIterator::from_transaction_cf(txn, cf_handle, readopts),
+            direction: Direction::Forward, // blown away by set_mode()
+            just_seeked: false,
+        };
+        rv.set_mode(mode);
+        rv
+    }
+
     pub fn set_mode(&mut self, mode: IteratorMode) {
         match mode {
             IteratorMode::Start => {
@@ -337,7 +446,7 @@ impl TransactionDBIterator {
 ///     println!("Saw {:?} {:?}", key, value);
 /// }
 /// ```
-impl Iterator for TransactionDBIterator {
+impl<'a> Iterator for TransactionDBIterator<'a> {
     type Item = KVBytes;
 
     fn next(&mut self) -> Option<KVBytes> {
@@ -364,8 +473,8 @@ impl Iterator for TransactionDBIterator {
     }
 }
 
-impl Into<TransactionDBRawIterator> for TransactionDBIterator {
-    fn into(self) -> TransactionDBRawIterator {
+impl<'a> Into<TransactionDBRawIterator<'a>> for TransactionDBIterator<'a> {
+    fn into(self) -> TransactionDBRawIterator<'a> {
         self.raw
     }
 }
@@ -54,8 +54,12 @@ mod ffi_util;
 
 pub mod backup;
 pub mod checkpoint;
-mod comparator;
+pub mod comparator;
 pub mod write_options;
+pub mod flush_options;
+pub mod compact_range_options;
+pub mod ingest_external_file_options;
+pub mod sst_file_writer;
 pub mod write_batch;
 pub mod column_family;
 pub mod blocked_based_options;
@@ -63,6 +67,7 @@ pub mod dbvector;
 pub mod iterator;
 pub mod read_options;
 pub mod snapshot;
+pub mod snapshot_transaction;
 pub mod merge_operator;
 pub mod compaction_filter;
 mod db;
@@ -72,17 +77,24 @@ pub mod transaction;
 pub mod transaction_db;
 pub mod transaction_options;
 pub mod transaction_db_options;
+pub mod optimistic_transaction_db;
+pub mod optimistic_transaction_options;
 mod slice_transform;
+pub mod properties;
 
 pub use compaction_filter::Decision as CompactionDecision;
 pub use db::{new_bloom_filter};
 
-pub use slice_transform::SliceTransform;
+pub use slice_transform::{InDomainFn, SliceTransform, TransformFn};
+pub use properties::Property;
+pub use snapshot::AsSnapshot;
 
 pub use merge_operator::MergeOperands;
+pub use write_batch::WriteBatchIterator;
 use std::collections::BTreeMap;
 use std::error;
 use std::fmt;
+use std::marker::PhantomData;
 use std::path::PathBuf;
 
 /// A RocksDB database.
@@ -94,8 +106,27 @@ pub struct DB {
     path: PathBuf,
 }
 
+/// A RocksDB database with optimistic concurrency control.
+///
+/// Transactions take no locks. Conflicts are detected when a transaction
+/// commits, and the commit then fails with a `Busy` error.
+pub struct OptimisticTransactionDB {
+    inner: *mut ffi::rocksdb_optimistictransactiondb_t,
+    base: *mut ffi::rocksdb_t,
+    cfs: BTreeMap<String, ColumnFamily>,
+    path: PathBuf,
+}
+
+/// A RocksDB database with pessimistic concurrency control.
+///
+/// The C API of the pinned librocksdb-sys (RocksDB 6.1) gives no access to the
+/// database under a `TransactionDB`, so the property accessors
+/// (`property_value`, `property_int_value` and their `_cf` variants),
+/// memtable flushes (`flush`, `flush_cf` and their `_opt` variants) and
+/// manual compactions (the `compact_range` methods) are only available on `DB`.
 pub struct TransactionDB {
     inner: *mut ffi::rocksdb_transactiondb_t,
+    cfs: BTreeMap<String, ColumnFamily>,
     path: PathBuf,
 }
 
@@ -107,21 +138,86 @@ pub struct ColumnFamilyDescriptor {
     options: Options,
 }
 
+/// The category of an `Error`, taken from the RocksDB status it was built from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    NotFound,
+    Corruption,
+    NotSupported,
+    InvalidArgument,
+    IOError,
+    MergeInProgress,
+    /// The operation could not complete without blocking or doing I/O.
+    Incomplete,
+    ShutdownInProgress,
+    /// A lock or mutex could not be acquired in time.
+    TimedOut,
+    Aborted,
+    /// A write conflicted with another writer.
+    Busy,
+    /// A transaction was aborted to break a deadlock.
+    Deadlock,
+    Expired,
+    TryAgain,
+    /// A status this crate does not recognise, or an error raised by this crate
+    /// that fits none of the kinds above.
+    Other,
+}
+
 /// A simple wrapper round a string, used for errors reported from
 /// ffi calls.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
     message: String,
+    kind: ErrorKind,
 }
 
 impl Error {
     fn new(message: String) -> Error {
-        Error { message: message }
+        let kind = ffi_util::error_kind(&message);
+        Error { message: message, kind: kind }
+    }
+
+    /// For errors raised by this crate, which carry no RocksDB status to
+    /// take the kind from.
+    fn with_kind(kind: ErrorKind, message: String) -> Error {
+        Error { message: message, kind: kind }
     }
 
     pub fn to_string(self) -> String {
         self.into()
     }
+
+    /// Returns the kind of failure, as reported by RocksDB.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns true if the operation failed because of a write conflict, as
+    /// reported when committing an optimistic transaction.
+    pub fn is_busy(&self) -> bool {
+        self.kind == ErrorKind::Busy
+    }
+
+    /// Returns true if the operation failed because of a conflict with a
+    /// concurrent writer, so running it again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind {
+            ErrorKind::Busy | ErrorKind::TimedOut | ErrorKind::Deadlock | ErrorKind::TryAgain => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if a write was rejected because writes are stalled, which
+    /// only happens when `WriteOptions::set_no_slowdown` is set. Such writes fail
+    /// with `ErrorKind::Incomplete`; the caller can shed load or try again later.
+    pub fn is_write_stall(&self) -> bool {
+        // the statuses returned by `DBImpl::DelayWrite` and `ThrottleLowPriWritesIfNeeded`
+        const WRITE_STALL: &str = "Result incomplete: Write stall";
+        const LOW_PRI_WRITE_STALL: &str = "Result incomplete: Low priority write stall";
+        self.kind == ErrorKind::Incomplete
+            && (self.message == WRITE_STALL || self.message == LOW_PRI_WRITE_STALL)
+    }
 }
 
 impl AsRef<str> for Error {
@@ -216,6 +312,11 @@ pub struct TransactionDBOptions {
     inner: *mut ffi::rocksdb_transactiondb_options_t,
 }
 
+/// A transaction on a `TransactionDB` or an `OptimisticTransactionDB`.
+///
+/// The C API of the pinned librocksdb-sys (RocksDB 6.1) has neither single
+/// deletes nor `UndoGetForUpdate` for transactions, so there is no
+/// `single_delete`, `single_delete_cf` or `undo_get_for_update`.
 pub struct Transaction {
     inner: *mut ffi::rocksdb_transaction_t,
 }
@@ -224,11 +325,96 @@ pub struct TransactionOptions {
     inner: *mut ffi::rocksdb_transaction_options_t,
 }
 
+pub struct OptimisticTransactionOptions {
+    inner: *mut ffi::rocksdb_optimistictransaction_options_t,
+}
+
 
 pub struct WriteOptions {
     inner: *mut ffi::rocksdb_writeoptions_t,
 }
 
+/// Options for `DB::flush_opt` and `DB::flush_cf_opt`.
+///
+/// A `TransactionDB` cannot be flushed, see its documentation.
+///
+/// # Examples
+///
+/// Flushing without waiting for the flush to finish:
+///
+/// ```
+/// use rocksdb::{DB, FlushOptions};
+///
+/// let db = DB::open_default("path/for/rocksdb/storage_flush").unwrap();
+/// db.put(b"my key", b"my value").unwrap();
+///
+/// let mut flush_options = FlushOptions::default();
+/// flush_options.set_wait(false);
+///
+/// db.flush_opt(&flush_options).unwrap();
+/// ```
+pub struct FlushOptions {
+    inner: *mut ffi::rocksdb_flushoptions_t,
+}
+
+/// Options for `DB::compact_range_opt` and `DB::compact_range_cf_opt`.
+///
+/// The C API of the pinned librocksdb-sys (RocksDB 6.1) cannot set the number
+/// of parallel subcompactions of a manual compaction, and a `TransactionDB`
+/// cannot be compacted manually.
+///
+/// # Examples
+///
+/// Compacting everything into the last level, even if it is already there:
+///
+/// ```
+/// use rocksdb::{BottommostLevelCompaction, CompactRangeOptions, DB};
+///
+/// let db = DB::open_default("path/for/rocksdb/storage_compact").unwrap();
+///
+/// let mut compact_options = CompactRangeOptions::default();
+/// compact_options.set_exclusive_manual_compaction(false);
+/// compact_options.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+///
+/// db.compact_range_opt(None, None, &compact_options);
+/// ```
+pub struct CompactRangeOptions {
+    inner: *mut ffi::rocksdb_compactoptions_t,
+}
+
+/// Options for `DB::ingest_external_file_opt`.
+pub struct IngestExternalFileOptions {
+    inner: *mut ffi::rocksdb_ingestexternalfileoptions_t,
+}
+
+/// Builds an SST file outside of any database, to be added to one with
+/// `DB::ingest_external_file`.
+///
+/// Keys must be added in increasing order, according to the comparator of
+/// the `Options` the writer was created with.
+///
+/// ```
+/// use rocksdb::{DB, Options, SstFileWriter};
+/// use std::fs;
+///
+/// // the writer does not create the directory of the file
+/// fs::create_dir_all("path/for/rocksdb").unwrap();
+/// let opts = Options::default();
+/// let mut writer = SstFileWriter::create(&opts);
+/// writer.open("path/for/rocksdb/bulk.sst").unwrap();
+/// writer.put(b"k1", b"v1").unwrap();
+/// writer.put(b"k2", b"v2").unwrap();
+/// writer.finish().unwrap();
+///
+/// let db = DB::open_default("path/for/rocksdb/storage_ingest").unwrap();
+/// db.ingest_external_file(&["path/for/rocksdb/bulk.sst"]).unwrap();
+/// ```
+pub struct SstFileWriter<'a> {
+    inner: *mut ffi::rocksdb_sstfilewriter_t,
+    // The writer refers to the options it was created with.
+    phantom: PhantomData<&'a Options>,
+}
+
 
 /// An opaque type used to represent a column family. Returned from some functions, and used
 /// in others
@@ -263,6 +449,32 @@ pub enum DBRecoveryMode {
     SkipAnyCorruptedRecord = ffi::rocksdb_skip_any_corrupted_records_recovery as isize,
 }
 
+/// Whether a manual compaction rewrites files in the bottommost level, set with
+/// `CompactRangeOptions::set_bottommost_level_compaction`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BottommostLevelCompaction {
+    /// Skip the bottommost level.
+    Skip = 0,
+    /// Compact the bottommost level only if a compaction filter is set.
+    IfHaveCompactionFilter = 1,
+    /// Always compact the bottommost level.
+    Force = 2,
+}
+
+/// Which data a read is allowed to look at, set with `ReadOptions::set_read_tier`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReadTier {
+    /// Read from the memtables, block cache and storage.
+    All = 0,
+    /// Read only from the memtables and block cache. Reads that would need I/O
+    /// fail with an `Incomplete` error.
+    BlockCache = 1,
+    /// Read only persisted data. Used when the WAL is disabled.
+    Persisted = 2,
+    /// Read only from the memtables.
+    Memtable = 3,
+}
+
 
 pub struct WriteBatch {
     inner: *mut ffi::rocksdb_writebatch_t,
@@ -270,6 +482,8 @@ pub struct WriteBatch {
 
 pub struct ReadOptions {
     inner: *mut ffi::rocksdb_readoptions_t,
+    iterate_upper_bound: Option<Vec<u8>>,
+    iterate_lower_bound: Option<Vec<u8>>,
 }
 
 pub struct Snapshot<'a> {
@@ -277,13 +491,23 @@ pub struct Snapshot<'a> {
     inner: *const ffi::rocksdb_snapshot_t,
 }
 
-pub struct TransactionSnapshot {
+pub struct TransactionSnapshot<'a> {
+    source: SnapshotSource<'a>,
     inner: *const ffi::rocksdb_snapshot_t,
 }
 
+// Where a `TransactionSnapshot` was taken, which decides how it is read and released.
+enum SnapshotSource<'a> {
+    Db(&'a TransactionDB),
+    Transaction(&'a Transaction),
+}
+
 
 pub struct DBRawIterator {
     inner: *mut ffi::rocksdb_iterator_t,
+    // Kept alive for the iterator, which refers to the bounds stored in it,
+    // when the iterator owns its options, see `DB::range_iterator`.
+    _readopts: Option<ReadOptions>,
 }
 
 
@@ -293,13 +517,15 @@ pub struct DBIterator {
     just_seeked: bool,
 }
 
-pub struct TransactionDBRawIterator {
+pub struct TransactionDBRawIterator<'a> {
     inner: *mut ffi::rocksdb_iterator_t,
+    // The `TransactionDB` or `Transaction` the iterator reads from.
+    source: PhantomData<&'a ()>,
 }
 
 
-pub struct TransactionDBIterator {
-    raw: TransactionDBRawIterator,
+pub struct TransactionDBIterator<'a> {
+    raw: TransactionDBRawIterator<'a>,
     direction: Direction,
     just_seeked: bool,
 }
@@ -60,24 +60,42 @@ use std::mem;
 use std::ptr;
 use std::slice;
 
-pub type MergeFn = fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>>;
+/// Function to merge operands with.
+///
+/// This function takes the key, the existing value if any, and the operands to
+/// apply, and returns the merged value, or `None` if the merge failed.
+///
+/// Closures may capture configuration or shared state. They can be called from
+/// several threads at once, so they must be `Send + Sync`.
+pub trait MergeFn: Fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>> + Send + Sync + 'static {}
+impl<F> MergeFn for F
+where
+	F: Fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>>,
+	F: Send + Sync + 'static,
+{
+}
+
+/// Plain function form of `MergeFn`. Two different functions passed to
+/// `Options::set_merge_operator` need to be cast to it to share a type.
+pub type MergeFnPtr = fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>>;
 
-pub struct MergeOperatorCallback {
+pub struct MergeOperatorCallback<F: MergeFn> {
 	pub name: CString,
-	pub full_merge_fn: MergeFn,
-	pub partial_merge_fn: MergeFn,
+	pub full_merge_fn: F,
+	/// Falls back to `full_merge_fn` when unset.
+	pub partial_merge_fn: Option<F>,
 }
 
-pub unsafe extern "C" fn destructor_callback(raw_cb: *mut c_void) {
-	let _: Box<MergeOperatorCallback> = mem::transmute(raw_cb);
+pub unsafe extern "C" fn destructor_callback<F: MergeFn>(raw_cb: *mut c_void) {
+	let _: Box<MergeOperatorCallback<F>> = mem::transmute(raw_cb);
 }
 
-pub unsafe extern "C" fn name_callback(raw_cb: *mut c_void) -> *const c_char {
-	let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+pub unsafe extern "C" fn name_callback<F: MergeFn>(raw_cb: *mut c_void) -> *const c_char {
+	let cb = &*(raw_cb as *const MergeOperatorCallback<F>);
 	cb.name.as_ptr()
 }
 
-pub unsafe extern "C" fn full_merge_callback(
+pub unsafe extern "C" fn full_merge_callback<F: MergeFn>(
 	raw_cb: *mut c_void,
 	raw_key: *const c_char,
 	key_len: size_t,
@@ -89,7 +107,7 @@ pub unsafe extern "C" fn full_merge_callback(
 	success: *mut u8,
 	new_value_length: *mut size_t,
 	) -> *mut c_char {
-	let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+	let cb = &*(raw_cb as *const MergeOperatorCallback<F>);
 	let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
 	let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
 	let oldval = 
@@ -113,7 +131,7 @@ pub unsafe extern "C" fn full_merge_callback(
 	}
 }
 
-pub unsafe extern "C" fn partial_merge_callback(
+pub unsafe extern "C" fn partial_merge_callback<F: MergeFn>(
 	raw_cb: *mut c_void,
 	raw_key: *const c_char,
 	key_len: size_t,
@@ -123,10 +141,14 @@ pub unsafe extern "C" fn partial_merge_callback(
 	success: *mut u8,
 	new_value_length: *mut size_t,
 	) -> *mut c_char {
-	let cb = &mut *(raw_cb as *mut MergeOperatorCallback);
+	let cb = &*(raw_cb as *const MergeOperatorCallback<F>);
 	let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
 	let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-	if let Some(mut result) = (cb.partial_merge_fn)(key, None, operands) {
+	let merged = match cb.partial_merge_fn {
+		Some(ref partial_merge_fn) => partial_merge_fn(key, None, operands),
+		None => (cb.full_merge_fn)(key, None, operands),
+	};
+	if let Some(mut result) = merged {
 		result.shrink_to_fit();
 		// TODO(tan) investigate zero-copy techniques to improve performance
 		let buf = libc::malloc(result.len() as size_t);
@@ -345,7 +367,7 @@ mod test {
 		opts.set_compaction_style(DBCompactionStyle::Universal);
 		opts.set_min_write_buffer_number_to_merge(10);
 
-		opts.set_merge_operator("sort operator", test_counting_full_merge, Some(test_counting_partial_merge));
+		opts.set_merge_operator("sort operator", test_counting_full_merge as MergeFnPtr, Some(test_counting_partial_merge as MergeFnPtr));
 		{
 			let db = Arc::new(DB::open(&opts, path).unwrap());
 			let _ = db.delete(b"k1");
@@ -453,4 +475,38 @@ mod test {
 		}
 		assert!(DB::destroy(&opts, path).is_ok());
 	}
+
+	#[test]
+	fn closure_mergetest() {
+		use std::sync::Arc;
+		use std::sync::atomic::{AtomicUsize, Ordering};
+		use {DB, Options};
+
+		let path = "_rust_rocksdb_closure_mergetest";
+		let max_len = 4;
+		let calls = Arc::new(AtomicUsize::new(0));
+		let counter = calls.clone();
+		let mut opts = Options::default();
+		opts.create_if_missing(true);
+		// keeps only the `max_len` most recent bytes
+		opts.set_merge_operator("capped append", move |_key, existing_val, operands| {
+			counter.fetch_add(1, Ordering::SeqCst);
+			let mut result: Vec<u8> = existing_val.map(|v| v.to_vec()).unwrap_or_default();
+			for op in operands {
+				result.extend_from_slice(op);
+			}
+			let start = result.len().saturating_sub(max_len);
+			Some(result[start..].to_vec())
+		}, None);
+		{
+			let db = DB::open(&opts, path).unwrap();
+			let _ = db.put(b"k1", b"a");
+			let _ = db.merge(b"k1", b"bc");
+			let _ = db.merge(b"k1", b"def");
+			let r = db.get(b"k1");
+			assert!(r.unwrap().unwrap().to_utf8().unwrap() == "cdef");
+		}
+		assert!(calls.load(Ordering::SeqCst) > 0);
+		assert!(DB::destroy(&opts, path).is_ok());
+	}
 }
@@ -0,0 +1,408 @@
+use {OptimisticTransactionDB, DB, Error, Options, OptimisticTransactionOptions, Transaction, IteratorMode, WriteBatch, ReadOptions, DBVector, DBIterator, DBRawIterator, WriteOptions, ColumnFamily, ColumnFamilyDescriptor};
+use ffi;
+use ffi_util::open_column_families;
+
+use libc::{c_char, size_t};
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::ptr;
+
+unsafe impl Send for OptimisticTransactionDB {}
+unsafe impl Sync for OptimisticTransactionDB {}
+
+/// A database whose transactions are validated when they commit.
+///
+/// ```
+/// use rocksdb::{OptimisticTransactionDB, OptimisticTransactionOptions, WriteOptions};
+///
+/// let db = OptimisticTransactionDB::open_default("path/for/rocksdb/storage_optimistic").unwrap();
+/// let mut txn = db.begin(&WriteOptions::default(), &OptimisticTransactionOptions::default()).unwrap();
+/// txn.put(b"my key", b"my value").unwrap();
+/// match txn.commit() {
+///     Ok(()) => println!("committed"),
+///     Err(ref e) if e.is_busy() => println!("conflict, retry"),
+///     Err(e) => println!("operational problem encountered: {}", e),
+/// }
+/// ```
+impl OptimisticTransactionDB {
+    /// Open a database with default options.
+    pub fn open_default<P: AsRef<Path>>(path: P) -> Result<OptimisticTransactionDB, Error> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        OptimisticTransactionDB::open(&opts, path)
+    }
+
+    /// Open the database with the specified options.
+    pub fn open<P: AsRef<Path>>(opts: &Options, path: P) -> Result<OptimisticTransactionDB, Error> {
+        OptimisticTransactionDB::open_cf(opts, path, &[])
+    }
+
+    /// Open a database with the given database options and column family names.
+    ///
+    /// Column families opened using this function will be created with default `Options`.
+    pub fn open_cf<P: AsRef<Path>>(opts: &Options, path: P, cfs: &[&str]) -> Result<OptimisticTransactionDB, Error> {
+        let cfs_v = cfs.to_vec().iter().map(|name| ColumnFamilyDescriptor::new(*name, Options::default())).collect();
+
+        OptimisticTransactionDB::open_cf_descriptors(opts, path, cfs_v)
+    }
+
+    /// Open a database with the given database options and column family names/options.
+    pub fn open_cf_descriptors<P: AsRef<Path>>(opts: &Options, path: P, cfs: Vec<ColumnFamilyDescriptor>) -> Result<OptimisticTransactionDB, Error> {
+        let path = path.as_ref();
+        let cpath = match CString::new(path.to_string_lossy().as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err(Error::new(
+                    "Failed to convert path to CString \
+                                       when opening DB."
+                        .to_owned(),
+                ))
+            }
+        };
+
+        if let Err(e) = fs::create_dir_all(&path) {
+            return Err(Error::new(format!(
+                "Failed to create RocksDB\
+                                           directory: `{:?}`.",
+                e
+            )));
+        }
+
+        let (db, cf_map) = if cfs.len() == 0 {
+            let db: *mut ffi::rocksdb_optimistictransactiondb_t = unsafe {
+                ffi_try!(ffi::rocksdb_optimistictransactiondb_open(opts.inner, cpath.as_ptr() as *const _,))
+            };
+            (db, BTreeMap::new())
+        } else {
+            try!(open_column_families(cfs, |n, names, cfopts, cfhandles| unsafe {
+                Ok(ffi_try!(ffi::rocksdb_optimistictransactiondb_open_column_families(
+                    opts.inner,
+                    cpath.as_ptr(),
+                    n,
+                    names,
+                    cfopts,
+                    cfhandles,)))
+            }))
+        };
+
+        if db.is_null() {
+            return Err(Error::new("Could not initialize database.".to_owned()));
+        }
+
+        // The base DB is used for reads and writes made outside a transaction.
+        let base = unsafe { ffi::rocksdb_optimistictransactiondb_get_base_db(db) };
+
+        Ok(OptimisticTransactionDB {
+            inner: db,
+            base: base,
+            cfs: cf_map,
+            path: path.to_path_buf(),
+        })
+    }
+
+    pub fn list_cf<P: AsRef<Path>>(opts: &Options, path: P) -> Result<Vec<String>, Error> {
+        DB::list_cf(opts, path)
+    }
+
+    pub fn destroy<P: AsRef<Path>>(opts: &Options, path: P) -> Result<(), Error> {
+        DB::destroy(opts, path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path.as_path()
+    }
+
+    /// Begins a new transaction. Its writes are checked for conflicts with other
+    /// writers on `commit`, which fails with a `Busy` error if any are found.
+    pub fn begin(&self, write_options: &WriteOptions, txn_options: &OptimisticTransactionOptions) -> Result<Transaction, Error> {
+        unsafe {
+            let transaction = ffi::rocksdb_optimistictransaction_begin(self.inner, write_options.inner, txn_options.inner, ptr::null_mut());
+            if transaction.is_null() {
+                return Err(Error::new(
+                    "Failed to Create transaction"
+                        .to_owned(),
+                ))
+            } else {
+                Ok(Transaction {
+                    inner: transaction
+                })
+            }
+        }
+    }
+
+    pub fn begin_with_txn(&self, write_options: &WriteOptions, txn_options: &OptimisticTransactionOptions, old_txn: &Transaction) -> Result<Transaction, Error> {
+        unsafe {
+            let transaction = ffi::rocksdb_optimistictransaction_begin(self.inner, write_options.inner, txn_options.inner, old_txn.inner);
+            if transaction.is_null() {
+                return Err(Error::new(
+                    "Failed to Create transaction"
+                        .to_owned(),
+                ))
+            } else {
+                Ok(Transaction {
+                    inner: transaction,
+                })
+            }
+        }
+    }
+
+    pub fn create_cf(&mut self, name: &str, opts: &Options) -> Result<ColumnFamily, Error> {
+        let cname = match CString::new(name.as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err(Error::new(
+                    "Failed to convert path to CString \
+                                       when opening rocksdb"
+                        .to_owned(),
+                ))
+            }
+        };
+        let cf = unsafe {
+            let cf_handler = ffi_try!(ffi::rocksdb_create_column_family(
+                self.base,
+                opts.inner,
+                cname.as_ptr(),
+            ));
+            let cf = ColumnFamily { inner: cf_handler };
+            self.cfs.insert(name.to_string(), cf);
+            cf
+        };
+        Ok(cf)
+    }
+
+    pub fn drop_cf(&mut self, name: &str) -> Result<(), Error> {
+        let cf = match self.cfs.get(name) {
+            Some(cf) => *cf,
+            None => {
+                return Err(Error::new(
+                    format!("Invalid column family: {}", name).to_owned(),
+                ))
+            }
+        };
+        unsafe {
+            ffi_try!(ffi::rocksdb_drop_column_family(
+                self.base,
+                cf.inner,
+            ));
+            ffi::rocksdb_column_family_handle_destroy(cf.inner);
+        }
+        self.cfs.remove(name);
+        Ok(())
+    }
+
+    /// Return the underlying column family handle.
+    pub fn cf_handle(&self, name: &str) -> Option<ColumnFamily> {
+        self.cfs.get(name).cloned()
+    }
+
+    pub fn write_opt(&self, batch: WriteBatch, writeopts: &WriteOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_write(self.base, writeopts.inner, batch.inner,));
+        }
+        Ok(())
+    }
+
+    pub fn write(&self, batch: WriteBatch) -> Result<(), Error> {
+        self.write_opt(batch, &WriteOptions::default())
+    }
+
+    pub fn get_opt(&self, key: &[u8], readopts: &ReadOptions) -> Result<Option<DBVector>, Error> {
+        unsafe {
+            let mut val_len: size_t = 0;
+            let val = ffi_try!(ffi::rocksdb_get(
+                self.base,
+                readopts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            )) as *mut u8;
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val, val_len)))
+            }
+        }
+    }
+
+    /// Return the bytes associated with a key value
+    pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, Error> {
+        self.get_opt(key, &ReadOptions::default())
+    }
+
+    pub fn get_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBVector>, Error> {
+        unsafe {
+            let mut val_len: size_t = 0;
+            let val = ffi_try!(ffi::rocksdb_get_cf(
+                self.base,
+                readopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            )) as *mut u8;
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val, val_len)))
+            }
+        }
+    }
+
+    pub fn get_cf(&self, cf: ColumnFamily, key: &[u8]) -> Result<Option<DBVector>, Error> {
+        self.get_cf_opt(cf, key, &ReadOptions::default())
+    }
+
+    pub fn put_opt(&self, key: &[u8], value: &[u8], writeopts: &WriteOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_put(
+                self.base,
+                writeopts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn put_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        key: &[u8],
+        value: &[u8],
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_put_cf(
+                self.base,
+                writeopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn merge_opt(&self, key: &[u8], value: &[u8], writeopts: &WriteOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_merge(
+                self.base,
+                writeopts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn delete_opt(&self, key: &[u8], writeopts: &WriteOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_delete(
+                self.base,
+                writeopts.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn delete_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        key: &[u8],
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_delete_cf(
+                self.base,
+                writeopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        self.put_opt(key, value, &WriteOptions::default())
+    }
+
+    pub fn put_cf(&self, cf: ColumnFamily, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        self.put_cf_opt(cf, key, value, &WriteOptions::default())
+    }
+
+    pub fn merge(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        self.merge_opt(key, value, &WriteOptions::default())
+    }
+
+    pub fn delete(&self, key: &[u8]) -> Result<(), Error> {
+        self.delete_opt(key, &WriteOptions::default())
+    }
+
+    pub fn delete_cf(&self, cf: ColumnFamily, key: &[u8]) -> Result<(), Error> {
+        self.delete_cf_opt(cf, key, &WriteOptions::default())
+    }
+
+    pub fn iterator(&self, mode: IteratorMode) -> DBIterator {
+        DBIterator::from_raw(self.raw_iterator(), mode)
+    }
+
+    pub fn iterator_cf(&self, cf_handle: ColumnFamily, mode: IteratorMode) -> Result<DBIterator, Error> {
+        Ok(DBIterator::from_raw(try!(self.raw_iterator_cf(cf_handle)), mode))
+    }
+
+    pub fn raw_iterator(&self) -> DBRawIterator {
+        let opts = ReadOptions::default();
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_create_iterator(self.base, opts.inner),
+                _readopts: Some(opts),
+            }
+        }
+    }
+
+    pub fn raw_iterator_cf(&self, cf_handle: ColumnFamily) -> Result<DBRawIterator, Error> {
+        let opts = ReadOptions::default();
+        unsafe {
+            Ok(DBRawIterator {
+                inner: ffi::rocksdb_create_iterator_cf(self.base, opts.inner, cf_handle.inner),
+                _readopts: Some(opts),
+            })
+        }
+    }
+}
+
+impl Drop for OptimisticTransactionDB {
+    fn drop(&mut self) {
+        unsafe {
+            for cf in self.cfs.values() {
+                ffi::rocksdb_column_family_handle_destroy(cf.inner);
+            }
+            // Only frees the base DB wrapper, the DB itself is closed below.
+            ffi::rocksdb_optimistictransactiondb_close_base_db(self.base);
+            ffi::rocksdb_optimistictransactiondb_close(self.inner);
+        }
+    }
+}
+
+impl fmt::Debug for OptimisticTransactionDB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RocksDB {{ path: {:?} }}", self.path())
+    }
+}
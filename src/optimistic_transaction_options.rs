@@ -0,0 +1,39 @@
+use libc::c_uchar;
+
+use ffi;
+use {OptimisticTransactionOptions};
+
+unsafe impl Send for OptimisticTransactionOptions {}
+
+impl Drop for OptimisticTransactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_optimistictransaction_options_destroy(self.inner);
+        }
+    }
+}
+
+impl OptimisticTransactionOptions {
+    pub fn new() -> OptimisticTransactionOptions {
+        OptimisticTransactionOptions::default()
+    }
+
+    /// Take a snapshot when the transaction begins. Conflicts are then checked
+    /// against writes made since the snapshot rather than since the first read
+    /// of each key.
+    pub fn set_snapshot(&mut self, value: bool) {
+        unsafe {
+            ffi::rocksdb_optimistictransaction_options_set_set_snapshot(self.inner, value as c_uchar);
+        }
+    }
+}
+
+impl Default for OptimisticTransactionOptions {
+    fn default() -> OptimisticTransactionOptions {
+        let transaction_opts = unsafe { ffi::rocksdb_optimistictransaction_options_create() };
+        if transaction_opts.is_null() {
+            panic!("Could not create RocksDB optimistic transaction options");
+        }
+        OptimisticTransactionOptions { inner: transaction_opts }
+    }
+}
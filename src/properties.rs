@@ -0,0 +1,70 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+/// Commonly used database properties, to be passed to `DB::property_value`
+/// and `DB::property_int_value`.
+///
+/// Any other property can be read by passing its full name, e.g.
+/// `"rocksdb.num-files-at-level0"`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Property {
+    /// Estimated number of keys in the column family.
+    EstimateNumKeys,
+    /// Total size in bytes of all SST files.
+    TotalSstFilesSize,
+    /// Estimated size in bytes of the live data.
+    EstimateLiveDataSize,
+    /// Approximate size in bytes of the active and unflushed immutable memtables.
+    CurSizeAllMemTables,
+    /// Number of immutable memtables that have not yet been flushed.
+    NumImmutableMemTable,
+    /// Number of currently running compactions.
+    NumRunningCompactions,
+    /// Number of currently running flushes.
+    NumRunningFlushes,
+    /// Number of unreleased snapshots.
+    NumSnapshots,
+    /// Memory size in bytes of the entries residing in the block cache.
+    BlockCacheUsage,
+    /// Capacity in bytes of the block cache.
+    BlockCacheCapacity,
+    /// Multi-line string with the statistics of the column family.
+    Stats,
+}
+
+impl Property {
+    /// Returns the full RocksDB name of the property.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Property::EstimateNumKeys => "rocksdb.estimate-num-keys",
+            Property::TotalSstFilesSize => "rocksdb.total-sst-files-size",
+            Property::EstimateLiveDataSize => "rocksdb.estimate-live-data-size",
+            Property::CurSizeAllMemTables => "rocksdb.cur-size-all-mem-tables",
+            Property::NumImmutableMemTable => "rocksdb.num-immutable-mem-table",
+            Property::NumRunningCompactions => "rocksdb.num-running-compactions",
+            Property::NumRunningFlushes => "rocksdb.num-running-flushes",
+            Property::NumSnapshots => "rocksdb.num-snapshots",
+            Property::BlockCacheUsage => "rocksdb.block-cache-usage",
+            Property::BlockCacheCapacity => "rocksdb.block-cache-capacity",
+            Property::Stats => "rocksdb.stats",
+        }
+    }
+}
+
+impl AsRef<str> for Property {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
@@ -1,5 +1,6 @@
-use {ReadOptions, Snapshot};
-use libc::{c_char, size_t, c_uchar};
+use {ReadOptions, ReadTier};
+use snapshot::AsSnapshot;
+use libc::{c_char, c_int, c_uchar, size_t, uint64_t};
 
 impl Drop for ReadOptions {
     fn drop(&mut self) {
@@ -8,28 +9,44 @@ impl Drop for ReadOptions {
 }
 
 impl ReadOptions {
-    // TODO add snapshot setting here
-    // TODO add snapshot wrapper structs with proper destructors;
-    // that struct needs an "iterator" impl too.
-    #[allow(dead_code)]
-    fn fill_cache(&mut self, v: bool) {
+    /// Reads through these options observe the state of the database as of `snapshot`.
+    ///
+    /// Accepts both `Snapshot` and `TransactionSnapshot`.
+    pub fn set_snapshot<S: AsSnapshot>(&mut self, snapshot: &S) {
         unsafe {
-            ffi::rocksdb_readoptions_set_fill_cache(self.inner, v as c_uchar);
+            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot.as_snapshot_ptr());
         }
     }
 
-    pub fn set_snapshot(&mut self, snapshot: &Snapshot) {
+    /// Sets the key at which iteration stops. The bound itself is not included.
+    ///
+    /// The key is copied and stored with these options, which must outlive
+    /// any iterator created from them. `DB::range_iterator` takes care of it.
+    pub fn set_iterate_upper_bound(&mut self, key: &[u8]) {
+        self.iterate_upper_bound = Some(key.to_vec());
+        let bound = self.iterate_upper_bound.as_ref().unwrap();
         unsafe {
-            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot.inner);
+            ffi::rocksdb_readoptions_set_iterate_upper_bound(
+                self.inner,
+                bound.as_ptr() as *const c_char,
+                bound.len() as size_t,
+            );
         }
     }
 
-    pub fn set_iterate_upper_bound(&mut self, key: &[u8]) {
+    /// Sets the first key iteration can return, seeking before it lands on
+    /// the bound instead.
+    ///
+    /// The key is copied and stored with these options, which must outlive
+    /// any iterator created from them. `DB::range_iterator` takes care of it.
+    pub fn set_iterate_lower_bound(&mut self, key: &[u8]) {
+        self.iterate_lower_bound = Some(key.to_vec());
+        let bound = self.iterate_lower_bound.as_ref().unwrap();
         unsafe {
-            ffi::rocksdb_readoptions_set_iterate_upper_bound(
+            ffi::rocksdb_readoptions_set_iterate_lower_bound(
                 self.inner,
-                key.as_ptr() as *const c_char,
-                key.len() as size_t,
+                bound.as_ptr() as *const c_char,
+                bound.len() as size_t,
             );
         }
     }
@@ -45,10 +62,95 @@ impl ReadOptions {
             ffi::rocksdb_readoptions_set_total_order_seek(self.inner, v as c_uchar)
         }
     }
+
+    /// If true, all data read from storage is checked against its checksum.
+    ///
+    /// Default: true
+    pub fn set_verify_checksums(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_verify_checksums(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Whether blocks read by this operation should be added to the block cache.
+    /// Disable it for bulk scans that would otherwise evict the hot set.
+    ///
+    /// Default: true
+    pub fn set_fill_cache(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_fill_cache(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Restricts reads to the given tier. With `ReadTier::BlockCache`, a read that
+    /// would need I/O fails with an `ErrorKind::Incomplete` error instead.
+    ///
+    /// Default: `ReadTier::All`
+    pub fn set_read_tier(&mut self, tier: ReadTier) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_read_tier(self.inner, tier as c_int);
+        }
+    }
+
+    /// Creates a tailing iterator, which sees data added after it was created.
+    ///
+    /// Default: false
+    pub fn set_tailing(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_tailing(self.inner, v as c_uchar);
+        }
+    }
+
+    /// If non-zero, iterators read ahead this many bytes from storage.
+    /// Useful for large scans on spinning disks.
+    ///
+    /// Default: 0
+    pub fn set_readahead_size(&mut self, v: usize) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_readahead_size(self.inner, v as size_t);
+        }
+    }
+
+    /// Keeps the blocks loaded by iterators pinned in memory as long as the
+    /// iterator is alive.
+    ///
+    /// Default: false
+    pub fn set_pin_data(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_pin_data(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Skips range tombstones when reading. Only safe when no range deletions
+    /// were issued, e.g. `DB::delete_range_cf`, or their keys are never read.
+    ///
+    /// Default: false
+    pub fn set_ignore_range_deletions(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_ignore_range_deletions(self.inner, v as c_uchar);
+        }
+    }
+
+    /// Makes a seek or next fail with an `Incomplete` error once it has skipped
+    /// this many internal keys, such as deleted or overwritten entries.
+    /// 0 means no limit.
+    ///
+    /// Default: 0
+    pub fn set_max_skippable_internal_keys(&mut self, v: u64) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_max_skippable_internal_keys(self.inner, v as uint64_t);
+        }
+    }
 }
 
 impl Default for ReadOptions {
     fn default() -> ReadOptions {
-        unsafe { ReadOptions { inner: ffi::rocksdb_readoptions_create() } }
+        unsafe {
+            ReadOptions {
+                inner: ffi::rocksdb_readoptions_create(),
+                iterate_upper_bound: None,
+                iterate_lower_bound: None,
+            }
+        }
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
 use std::ffi::CString;
 use std::mem;
 use std::ptr;
@@ -36,11 +37,37 @@ pub struct SliceTransform {
 // opening a DB. 
 
 impl SliceTransform {
-    pub fn create(
+    /// Creates a transform from `transform_fn`, which may be a closure capturing
+    /// the state it needs.
+    ///
+    /// `transform_fn` is only called on keys for which `in_domain_fn` returns
+    /// `true`; when `in_domain_fn` is `None` every key is in the domain. Use
+    /// `create_with_domain` to pass a closure as `in_domain_fn`.
+    pub fn create<F: TransformFn>(
         name: &str,
-        transform_fn: TransformFn,
-        in_domain_fn: Option<InDomainFn>,
+        transform_fn: F,
+        in_domain_fn: Option<fn(&[u8]) -> bool>,
+    ) -> SliceTransform {
+        SliceTransform::create_callback(name, transform_fn, in_domain_fn)
+    }
+
+    /// Creates a transform from closures, which may capture the state they need.
+    ///
+    /// `transform_fn` is only called on keys for which `in_domain_fn` returns `true`.
+    pub fn create_with_domain<F: TransformFn, D: InDomainFn>(
+        name: &str,
+        transform_fn: F,
+        in_domain_fn: D,
+    ) -> SliceTransform {
+        SliceTransform::create_callback(name, transform_fn, Some(in_domain_fn))
+    }
+
+    fn create_callback<F: TransformFn, D: InDomainFn>(
+        name: &str,
+        transform_fn: F,
+        in_domain_fn: Option<D>,
     ) -> SliceTransform{
+        let has_in_domain = in_domain_fn.is_some();
         let cb = Box::new(TransformCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             transform_fn: transform_fn,
@@ -50,21 +77,21 @@ impl SliceTransform {
         let st = unsafe {
              ffi::rocksdb_slicetransform_create(
                 mem::transmute(cb),
-                Some(slice_transform_destructor_callback),
-                Some(transform_callback),
+                Some(slice_transform_destructor_callback::<F, D>),
+                Some(transform_callback::<F, D>),
 
                 // this is ugly, but I can't get the compiler
                 // not to barf with "expected fn pointer, found fn item"
                 // without this. sorry.
-                if let Some(_) = in_domain_fn {
-                    Some(in_domain_callback)
+                if has_in_domain {
+                    Some(in_domain_callback::<F, D>)
                 } else {
                     None
                 },
 
                 // this None points to the deprecated InRange callback
                 None,
-                Some(slice_transform_name_callback),
+                Some(slice_transform_name_callback::<F, D>),
             )
         };
 
@@ -73,6 +100,32 @@ impl SliceTransform {
         }
     }
 
+    /// Uses at most the first `len` bytes of a key as its prefix. Unlike
+    /// `create_fixed_prefix`, shorter keys are in the domain and are their own prefix.
+    pub fn create_capped_prefix(len: usize) -> SliceTransform {
+        SliceTransform::create_with_domain(
+            &format!("rust.CappedPrefix.{}", len),
+            move |key: &[u8]| key[..cmp::min(len, key.len())].to_vec(),
+            |_: &[u8]| true,
+        )
+    }
+
+    /// Uses everything up to and including the first `delimiter` byte of a key
+    /// as its prefix. Keys without the delimiter are not in the domain.
+    ///
+    /// With keys like `tenant\0table\0id`, `create_delimited_prefix(0)` groups
+    /// keys by tenant.
+    pub fn create_delimited_prefix(delimiter: u8) -> SliceTransform {
+        SliceTransform::create_with_domain(
+            &format!("rust.DelimitedPrefix.{}", delimiter),
+            move |key: &[u8]| match key.iter().position(|&b| b == delimiter) {
+                Some(pos) => key[..pos + 1].to_vec(),
+                None => key.to_vec(),
+            },
+            move |key: &[u8]| key.contains(&delimiter),
+        )
+    }
+
     pub fn create_fixed_prefix(len: size_t) -> SliceTransform {
         SliceTransform {
             inner: unsafe {
@@ -90,36 +143,45 @@ impl SliceTransform {
     }
 }
 
-pub type TransformFn = fn(&[u8]) -> Vec<u8>;
-pub type InDomainFn = fn(&[u8]) -> bool;
+/// Function computing the prefix of a key, given to `SliceTransform::create`.
+///
+/// Closures may capture the state they need. They can be called from several
+/// threads at once, so they must be `Send + Sync`.
+pub trait TransformFn: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static {}
+impl<F> TransformFn for F where F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static {}
+
+/// Function telling whether a key has a prefix, given to
+/// `SliceTransform::create_with_domain`.
+pub trait InDomainFn: Fn(&[u8]) -> bool + Send + Sync + 'static {}
+impl<F> InDomainFn for F where F: Fn(&[u8]) -> bool + Send + Sync + 'static {}
 
-pub struct TransformCallback {
+pub struct TransformCallback<F: TransformFn, D: InDomainFn> {
 	pub name: CString,
-	pub transform_fn: TransformFn,
-	pub in_domain_fn: Option<InDomainFn>,
+	pub transform_fn: F,
+	pub in_domain_fn: Option<D>,
 }
 
-pub unsafe extern "C" fn slice_transform_destructor_callback(
+pub unsafe extern "C" fn slice_transform_destructor_callback<F: TransformFn, D: InDomainFn>(
     raw_cb: *mut c_void
 ) {
-	let transform: Box<TransformCallback> = mem::transmute(raw_cb);
+	let transform: Box<TransformCallback<F, D>> = mem::transmute(raw_cb);
 	drop(transform);
 }
 
-pub unsafe extern "C" fn slice_transform_name_callback(
+pub unsafe extern "C" fn slice_transform_name_callback<F: TransformFn, D: InDomainFn>(
     raw_cb: *mut c_void
 ) -> *const c_char {
-	let cb = &mut *(raw_cb as *mut TransformCallback);
+	let cb = &*(raw_cb as *const TransformCallback<F, D>);
 	cb.name.as_ptr()
 }
 
-pub unsafe extern "C" fn transform_callback(
+pub unsafe extern "C" fn transform_callback<F: TransformFn, D: InDomainFn>(
 	raw_cb: *mut c_void,
 	raw_key: *const c_char,
 	key_len: size_t,
 	dst_length: *mut size_t,
 ) -> *mut c_char {
-	let cb = &mut *(raw_cb as *mut TransformCallback);
+	let cb = &*(raw_cb as *const TransformCallback<F, D>);
 	let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
 	let mut result = (cb.transform_fn)(key);
     result.shrink_to_fit();
@@ -133,15 +195,15 @@ pub unsafe extern "C" fn transform_callback(
     buf as *mut c_char
 }
 
-pub unsafe extern "C" fn in_domain_callback(
+pub unsafe extern "C" fn in_domain_callback<F: TransformFn, D: InDomainFn>(
     raw_cb: *mut c_void,
 	raw_key: *const c_char,
 	key_len: size_t,
 ) -> u8 {
-	let cb = &mut *(raw_cb as *mut TransformCallback);
+	let cb = &*(raw_cb as *const TransformCallback<F, D>);
 	let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
 
-    if (cb.in_domain_fn.unwrap())(key) {
+    if (cb.in_domain_fn.as_ref().unwrap())(key) {
         1
     } else {
         0
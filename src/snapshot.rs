@@ -1,5 +1,11 @@
 
 use {Error, DB, ColumnFamily, DBIterator, Snapshot, ReadOptions, DBVector, DBRawIterator, IteratorMode};
+use ffi;
+
+/// Implemented by the snapshot types that can be passed to `ReadOptions::set_snapshot`.
+pub trait AsSnapshot {
+    fn as_snapshot_ptr(&self) -> *const ffi::rocksdb_snapshot_t;
+}
 
 /// A consistent view of the database at the point of creation.
 ///
@@ -62,6 +68,12 @@ impl<'a> Snapshot<'a> {
     }
 }
 
+impl<'a> AsSnapshot for Snapshot<'a> {
+    fn as_snapshot_ptr(&self) -> *const ffi::rocksdb_snapshot_t {
+        self.inner
+    }
+}
+
 impl<'a> Drop for Snapshot<'a> {
     fn drop(&mut self) {
         unsafe {
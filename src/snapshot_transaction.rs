@@ -1,21 +1,35 @@
+use {Error, TransactionDB, Transaction, SnapshotSource, ColumnFamily, TransactionDBIterator, TransactionSnapshot, ReadOptions, DBVector, TransactionDBRawIterator, IteratorMode};
+use ffi;
+use snapshot::AsSnapshot;
 
-use {Error, TransactionDB, ColumnFamily, TransactionDBIterator, Snapshot, ReadOptions, DBVector, TransactionDBRawIterator, IteratorMode};
+use libc::c_void;
 
-/// A consistent view of the database at the point of creation.
+/// A consistent view of a transactional database at the point of creation.
 ///
 /// ```
-/// use rocksdb::{DB, IteratorMode};
+/// use rocksdb::{TransactionDB, IteratorMode};
 ///
-/// let db = DB::open_default("path/for/rocksdb/storage3").unwrap();
+/// let db = TransactionDB::open_default("path/for/rocksdb/storage_txn_snapshot").unwrap();
 /// let snapshot = db.snapshot(); // Creates a longer-term snapshot of the DB, but closed when goes out of scope
 /// let mut iter = snapshot.iterator(IteratorMode::Start); // Make as many iterators as you'd like from one snapshot
 /// ```
 ///
 
-impl TransactionSnapshot {
+impl<'a> TransactionSnapshot<'a> {
     pub fn new(db: &TransactionDB) -> TransactionSnapshot {
         let snapshot = unsafe { ffi::rocksdb_transactiondb_create_snapshot(db.inner) };
         TransactionSnapshot {
+            source: SnapshotSource::Db(db),
+            inner: snapshot,
+        }
+    }
+
+    /// Wraps the snapshot of a transaction. Reads go through the transaction, so
+    /// they also see its own uncommitted writes.
+    pub(crate) fn from_transaction(txn: &'a Transaction) -> TransactionSnapshot<'a> {
+        let snapshot = unsafe { ffi::rocksdb_transaction_get_snapshot(txn.inner) };
+        TransactionSnapshot {
+            source: SnapshotSource::Transaction(txn),
             inner: snapshot,
         }
     }
@@ -23,7 +37,10 @@ impl TransactionSnapshot {
     pub fn iterator(&self, mode: IteratorMode) -> TransactionDBIterator {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        TransactionDBIterator::new(self.db, &readopts, mode)
+        match self.source {
+            SnapshotSource::Db(db) => TransactionDBIterator::new(db, &readopts, mode),
+            SnapshotSource::Transaction(txn) => TransactionDBIterator::from_transaction(txn, &readopts, mode),
+        }
     }
 
     pub fn iterator_cf(
@@ -33,48 +50,81 @@ impl TransactionSnapshot {
     ) -> Result<TransactionDBIterator, Error> {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        TransactionDBIterator::new_cf(self.db, cf_handle, &readopts, mode)
+        match self.source {
+            SnapshotSource::Db(db) => TransactionDBIterator::new_cf(db, cf_handle, &readopts, mode),
+            SnapshotSource::Transaction(txn) => {
+                Ok(TransactionDBIterator::from_transaction_cf(txn, cf_handle, &readopts, mode))
+            }
+        }
     }
 
     pub fn raw_iterator(&self) -> TransactionDBRawIterator {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        TransactionDBRawIterator::new(self.db, &readopts)
+        match self.source {
+            SnapshotSource::Db(db) => TransactionDBRawIterator::new(db, &readopts),
+            SnapshotSource::Transaction(txn) => TransactionDBRawIterator::from_transaction(txn, &readopts),
+        }
     }
 
     pub fn raw_iterator_cf(&self, cf_handle: ColumnFamily) -> Result<TransactionDBRawIterator, Error> {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        TransactionDBRawIterator::new_cf(self.db, cf_handle, &readopts)
+        match self.source {
+            SnapshotSource::Db(db) => TransactionDBRawIterator::new_cf(db, cf_handle, &readopts),
+            SnapshotSource::Transaction(txn) => {
+                Ok(TransactionDBRawIterator::from_transaction_cf(txn, cf_handle, &readopts))
+            }
+        }
     }
 
     pub fn get(&self, key: &[u8]) -> Result<Option<DBVector>, Error> {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        self.db.get_opt(key, &readopts)
+        match self.source {
+            SnapshotSource::Db(db) => db.get_opt(key, &readopts),
+            SnapshotSource::Transaction(txn) => txn.get_opt(key, &readopts),
+        }
     }
 
     pub fn get_cf(&self, cf: ColumnFamily, key: &[u8]) -> Result<Option<DBVector>, Error> {
         let mut readopts = ReadOptions::default();
         readopts.set_snapshot(self);
-        self.db.get_cf_opt(cf, key, &readopts)
+        match self.source {
+            SnapshotSource::Db(db) => db.get_cf_opt(cf, key, &readopts),
+            SnapshotSource::Transaction(txn) => txn.get_cf_opt(cf, key, &readopts),
+        }
+    }
+}
+
+impl<'a> AsSnapshot for TransactionSnapshot<'a> {
+    fn as_snapshot_ptr(&self) -> *const ffi::rocksdb_snapshot_t {
+        self.inner
     }
 }
 
 impl<'a> Drop for TransactionSnapshot<'a> {
     fn drop(&mut self) {
         unsafe {
-            ffi::rocksdb_release_snapshot(self.db.inner, self.inner);
+            match self.source {
+                SnapshotSource::Db(db) => ffi::rocksdb_transactiondb_release_snapshot(db.inner, self.inner),
+                // The snapshot itself stays with the transaction, which releases it
+                // when it ends. c.h has no other call that frees only the wrapper;
+                // the release_snapshot calls would release the snapshot a second time.
+                SnapshotSource::Transaction(_) => ffi::rocksdb_free(self.inner as *mut c_void),
+            }
         }
     }
 }
 
 
 #[test]
-fn snapshot_test() {
-    let path = "_rust_rocksdb_snapshottest";
+fn transaction_snapshot_test() {
+    use {Options, Direction};
+
+    let path = "_rust_rocksdb_txn_snapshottest";
     {
-        let db = DB::open_default(path).unwrap();
+        let db = TransactionDB::open_default(path).unwrap();
         let p = db.put(b"k1", b"v1111");
         assert!(p.is_ok());
 
@@ -87,7 +137,16 @@ fn snapshot_test() {
 
         assert!(db.get(b"k2").unwrap().is_some());
         assert!(snap.get(b"k2").unwrap().is_none());
+
+        let keys: Vec<_> = snap.iterator(IteratorMode::Start).map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![b"k1".to_vec().into_boxed_slice()]);
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_snapshot(&snap);
+        assert!(db.get_opt(b"k2", &readopts).unwrap().is_none());
+        let iter = TransactionDBIterator::new(&db, &readopts, IteratorMode::From(b"k2", Direction::Reverse));
+        assert_eq!(iter.count(), 1);
     }
     let opts = Options::default();
-    assert!(DB::destroy(&opts, path).is_ok());
+    assert!(TransactionDB::destroy(&opts, path).is_ok());
 }
@@ -0,0 +1,119 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use {Error, Options, SstFileWriter};
+use ffi;
+
+use libc::{c_char, size_t};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::path::Path;
+
+impl<'a> SstFileWriter<'a> {
+    /// Creates a writer that builds files for databases opened with `opts`.
+    pub fn create(opts: &'a Options) -> SstFileWriter<'a> {
+        unsafe {
+            // the writer keeps its own copy of the env options
+            let env_options = ffi::rocksdb_envoptions_create();
+            let writer = ffi::rocksdb_sstfilewriter_create(env_options, opts.inner);
+            ffi::rocksdb_envoptions_destroy(env_options);
+            SstFileWriter {
+                inner: writer,
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    /// Starts a new file at `path`.
+    pub fn open<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let cpath = match CString::new(path.to_string_lossy().as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err(Error::new(
+                    "Failed to convert path to CString \
+                                       when opening SST file."
+                        .to_owned(),
+                ))
+            }
+        };
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_open(self.inner, cpath.as_ptr(),));
+            Ok(())
+        }
+    }
+
+    /// Finishes the file, which can then be ingested.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_finish(self.inner,));
+            Ok(())
+        }
+    }
+
+    /// Returns the size of the file written so far.
+    pub fn file_size(&self) -> u64 {
+        let mut size: u64 = 0;
+        unsafe {
+            ffi::rocksdb_sstfilewriter_file_size(self.inner, &mut size);
+        }
+        size
+    }
+
+    pub fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_put(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn merge(&mut self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_merge(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
+    pub fn delete(&mut self, key: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_delete(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+}
+
+impl<'a> Drop for SstFileWriter<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_sstfilewriter_destroy(self.inner);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use {TransactionDBRawIterator, TransactionDBIterator, Direction, ColumnFamily, ReadOptions, Error, DBVector, TransactionSnapshot, Transaction};
+use {TransactionDBRawIterator, TransactionDBIterator, IteratorMode, ColumnFamily, ReadOptions, Error, DBVector, TransactionSnapshot, Transaction};
 use libc::{c_uchar, size_t, c_char};
 
 unsafe impl Send for Transaction {}
@@ -54,12 +54,13 @@ impl Transaction {
         }
     }
 
+    /// Returns the snapshot taken by this transaction, if it was begun with
+    /// `TransactionOptions::set_snapshot(true)`.
+    ///
+    /// The snapshot stays owned by the transaction and is released when it ends,
+    /// so it cannot outlive it.
     pub fn get_snapshot(&self) -> Result<TransactionSnapshot, Error> {
-        unsafe {
-            Ok(TransactionSnapshot{
-                inner: ffi::rocksdb_transaction_get_snapshot(self.inner),
-            })
-        }
+        Ok(TransactionSnapshot::from_transaction(self))
     }
 
     pub fn get_opt(&self, key: &[u8], readopts: &ReadOptions) -> Result<Option<DBVector>, Error> {
@@ -134,6 +135,25 @@ impl Transaction {
         self.get_cf_opt(cf, key, &ReadOptions::default())
     }
 
+    /// Looks up several keys, seeing the transaction's own uncommitted writes.
+    /// Returns one result per key in the order of `keys`.
+    ///
+    /// This is not batched: the C API of the pinned librocksdb-sys (RocksDB 6.1)
+    /// has no `rocksdb_transaction_multi_get`, so it makes one `get` call per
+    /// key and saves none of the per-call overhead of `get_opt`. It stands in
+    /// until the sys crate provides the batched call.
+    pub fn multi_get_opt<K: AsRef<[u8]>>(
+        &self,
+        keys: &[K],
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<DBVector>, Error>> {
+        keys.iter().map(|key| self.get_opt(key.as_ref(), readopts)).collect()
+    }
+
+    pub fn multi_get<K: AsRef<[u8]>>(&self, keys: &[K]) -> Vec<Result<Option<DBVector>, Error>> {
+        self.multi_get_opt(keys, &ReadOptions::default())
+    }
+
     pub fn get_for_update_opt(&self, key: &[u8], readopts: &ReadOptions, exclusive: bool) -> Result<Option<DBVector>, Error> {
         if readopts.inner.is_null() {
             return Err(Error::new(
@@ -168,6 +188,47 @@ impl Transaction {
         self.get_for_update_opt(key, &ReadOptions::default(), exclusive)
     }
 
+    pub fn get_for_update_cf_opt(
+        &self,
+        cf: ColumnFamily,
+        key: &[u8],
+        readopts: &ReadOptions,
+        exclusive: bool,
+    ) -> Result<Option<DBVector>, Error> {
+        if readopts.inner.is_null() {
+            return Err(Error::new(
+                "Unable to create RocksDB read options. \
+                                   This is a fairly trivial call, and its \
+                                   failure may be indicative of a \
+                                   mis-compiled or mis-loaded RocksDB \
+                                   library."
+                    .to_owned(),
+            ));
+        }
+
+        unsafe {
+            let mut val_len: size_t = 0;
+            let val = ffi_try!(ffi::rocksdb_transaction_get_for_update_cf(
+                self.inner,
+                readopts.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+                exclusive as c_uchar,
+            )) as *mut u8;
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val, val_len)))
+            }
+        }
+    }
+
+    pub fn get_for_update_cf(&self, cf: ColumnFamily, key: &[u8], exclusive: bool) -> Result<Option<DBVector>, Error> {
+        self.get_for_update_cf_opt(cf, key, &ReadOptions::default(), exclusive)
+    }
+
     pub fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
         if key.len() == 0 {
             return Err(Error::new(
@@ -218,6 +279,20 @@ impl Transaction {
         }
     }
 
+    pub fn merge_cf(&self, cf: ColumnFamily, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_transaction_merge_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+
     pub fn delete(&self, key: &[u8]) -> Result<(), Error> {
        unsafe {
             ffi_try!(ffi::rocksdb_transaction_delete(
@@ -247,23 +322,34 @@ impl Transaction {
         }
     }
 
+    /// Creates an iterator positioned at the first key. It merges the
+    /// transaction's uncommitted writes over the database.
+    ///
+    /// The iterator refers to the iterate bounds stored in `readopts`, which
+    /// must then be kept alive as long as the iterator.
     pub fn create_iterator(&self, readopts: &ReadOptions) -> TransactionDBIterator {
-        unsafe {
-            TransactionDBIterator {
-                raw: TransactionDBRawIterator { inner: ffi::rocksdb_transaction_create_iterator(self.inner, readopts.inner) },
-                direction: Direction::Forward,
-                just_seeked: false,
-            }
-        }
+        TransactionDBIterator::from_transaction(self, readopts, IteratorMode::Start)
     }
 
+    /// Creates an iterator over `cf` positioned at its first key. It merges
+    /// the transaction's uncommitted writes to `cf` over the database.
     pub fn create_iterator_cf(&self, readopts: &ReadOptions, cf: ColumnFamily) -> TransactionDBIterator {
-        unsafe {
-            TransactionDBIterator {
-                raw: TransactionDBRawIterator { inner: ffi::rocksdb_transaction_create_iterator_cf(self.inner, readopts.inner, cf.inner) },
-                direction: Direction::Forward,
-                just_seeked: false,
-            }
-        }
+        TransactionDBIterator::from_transaction_cf(self, cf, readopts, IteratorMode::Start)
+    }
+
+    pub fn iterator(&self, mode: IteratorMode) -> TransactionDBIterator {
+        TransactionDBIterator::from_transaction(self, &ReadOptions::default(), mode)
+    }
+
+    pub fn iterator_cf(&self, cf: ColumnFamily, mode: IteratorMode) -> TransactionDBIterator {
+        TransactionDBIterator::from_transaction_cf(self, cf, &ReadOptions::default(), mode)
+    }
+
+    pub fn raw_iterator(&self) -> TransactionDBRawIterator {
+        TransactionDBRawIterator::from_transaction(self, &ReadOptions::default())
+    }
+
+    pub fn raw_iterator_cf(&self, cf: ColumnFamily) -> TransactionDBRawIterator {
+        TransactionDBRawIterator::from_transaction_cf(self, cf, &ReadOptions::default())
     }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
-use {TransactionDB, Error, Options, TransactionDBOptions, TransactionSnapshot, TransactionOptions, Transaction, IteratorMode, Direction, WriteBatch, ReadOptions, DBVector, TransactionDBIterator, TransactionDBRawIterator, WriteOptions, ColumnFamily};
+use {TransactionDB, Error, Options, TransactionDBOptions, TransactionSnapshot, TransactionOptions, Transaction, IteratorMode, Direction, WriteBatch, ReadOptions, DBVector, TransactionDBIterator, TransactionDBRawIterator, WriteOptions, ColumnFamily, ColumnFamilyDescriptor};
 use ffi;
+use ffi_util::open_column_families;
 
 use libc::{c_char, c_int, size_t};
+use std::collections::BTreeMap;
 use std::ffi::CString;
 use std::fmt;
 use std::fs;
@@ -29,8 +31,22 @@ impl TransactionDB {
         TransactionDB::open(&opts, &txopts, path)
     }
 
-    /// Open a database with the given database options and column family names/options.
+    /// Open the database with the specified options.
     pub fn open<P: AsRef<Path>>(opts: &Options, txopts: &TransactionDBOptions, path: P) -> Result<TransactionDB, Error> {
+        TransactionDB::open_cf(opts, txopts, path, &[])
+    }
+
+    /// Open a database with the given database options and column family names.
+    ///
+    /// Column families opened using this function will be created with default `Options`.
+    pub fn open_cf<P: AsRef<Path>>(opts: &Options, txopts: &TransactionDBOptions, path: P, cfs: &[&str]) -> Result<TransactionDB, Error> {
+        let cfs_v = cfs.to_vec().iter().map(|name| ColumnFamilyDescriptor::new(*name, Options::default())).collect();
+
+        TransactionDB::open_cf_descriptors(opts, txopts, path, cfs_v)
+    }
+
+    /// Open a database with the given database options and column family names/options.
+    pub fn open_cf_descriptors<P: AsRef<Path>>(opts: &Options, txopts: &TransactionDBOptions, path: P, cfs: Vec<ColumnFamilyDescriptor>) -> Result<TransactionDB, Error> {
         let path = path.as_ref();
         let cpath = match CString::new(path.to_string_lossy().as_bytes()) {
             Ok(c) => c,
@@ -51,11 +67,23 @@ impl TransactionDB {
             )));
         }
 
-        let db: *mut ffi::rocksdb_transactiondb_t;
-
-        unsafe {
-            db = ffi_try!(ffi::rocksdb_transactiondb_open(opts.inner, txopts.inner, cpath.as_ptr() as *const _,));
-        }
+        let (db, cf_map) = if cfs.len() == 0 {
+            let db: *mut ffi::rocksdb_transactiondb_t = unsafe {
+                ffi_try!(ffi::rocksdb_transactiondb_open(opts.inner, txopts.inner, cpath.as_ptr() as *const _,))
+            };
+            (db, BTreeMap::new())
+        } else {
+            try!(open_column_families(cfs, |n, names, cfopts, cfhandles| unsafe {
+                Ok(ffi_try!(ffi::rocksdb_transactiondb_open_column_families(
+                    opts.inner,
+                    txopts.inner,
+                    cpath.as_ptr(),
+                    n,
+                    names,
+                    cfopts,
+                    cfhandles,)))
+            }))
+        };
 
         if db.is_null() {
             return Err(Error::new("Could not initialize database.".to_owned()));
@@ -63,6 +91,7 @@ impl TransactionDB {
 
         Ok(TransactionDB {
             inner: db,
+            cfs: cf_map,
             path: path.to_path_buf(),
         })
     }
@@ -264,11 +293,17 @@ impl TransactionDB {
                 cname.as_ptr(),
             ));
             let cf = ColumnFamily { inner: cf_handler };
+            self.cfs.insert(name.to_string(), cf);
             cf
         };
         Ok(cf)
     }
 
+    /// Return the underlying column family handle.
+    pub fn cf_handle(&self, name: &str) -> Option<ColumnFamily> {
+        self.cfs.get(name).cloned()
+    }
+
     pub fn iterator(&self, mode: IteratorMode) -> TransactionDBIterator {
         let opts = ReadOptions::default();
         TransactionDBIterator::new(self, &opts, mode)
@@ -289,14 +324,28 @@ impl TransactionDB {
         TransactionDBIterator::new(self, &opts, IteratorMode::From(prefix, Direction::Forward))
     }
 
+    pub fn iterator_cf(
+        &self,
+        cf_handle: ColumnFamily,
+        mode: IteratorMode,
+    ) -> Result<TransactionDBIterator, Error> {
+        let opts = ReadOptions::default();
+        TransactionDBIterator::new_cf(self, cf_handle, &opts, mode)
+    }
+
     pub fn raw_iterator(&self) -> TransactionDBRawIterator {
         let opts = ReadOptions::default();
         TransactionDBRawIterator::new(self, &opts)
     }
 
-    // pub fn snapshot(&self) -> TransactionSnapshot {
-    //     TransactionSnapshot::new(self)
-    // }
+    pub fn raw_iterator_cf(&self, cf_handle: ColumnFamily) -> Result<TransactionDBRawIterator, Error> {
+        let opts = ReadOptions::default();
+        TransactionDBRawIterator::new_cf(self, cf_handle, &opts)
+    }
+
+    pub fn snapshot(&self) -> TransactionSnapshot {
+        TransactionSnapshot::new(self)
+    }
 
     pub fn put_opt(&self, key: &[u8], value: &[u8], writeopts: &WriteOptions) -> Result<(), Error> {
         unsafe {
@@ -406,9 +455,9 @@ impl TransactionDB {
 impl Drop for TransactionDB {
     fn drop(&mut self) {
         unsafe {
-            // for cf in self.cfs.values() {
-            //     ffi::rocksdb_column_family_handle_destroy(cf.inner);
-            // }
+            for cf in self.cfs.values() {
+                ffi::rocksdb_column_family_handle_destroy(cf.inner);
+            }
             ffi::rocksdb_transactiondb_close(self.inner);
         }
     }
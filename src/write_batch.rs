@@ -1,5 +1,122 @@
-use {WriteBatch, Error, ColumnFamily};
+use {WriteBatch, Error, ErrorKind, ColumnFamily};
 use libc::{size_t, c_char};
+use std::slice;
+
+/// Receives the operations recorded in a `WriteBatch`, in the order they were added.
+///
+/// Each callback gets the id of the column family the operation targets; the
+/// default column family has id `0`.
+pub trait WriteBatchIterator {
+    /// Called with a key and value that were `put` into the batch.
+    fn put(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+    /// Called with a key that was `delete`d from the batch. Single deletes are
+    /// reported here too.
+    fn delete(&mut self, cf_id: u32, key: &[u8]);
+    /// Called with a key and operand that were `merge`d into the batch.
+    fn merge(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+    /// Called with the range `["from", "to")` passed to `delete_range`.
+    fn delete_range(&mut self, cf_id: u32, from: &[u8], to: &[u8]);
+}
+
+// Record tags of the serialized batch, see `ValueType` in RocksDB's db/dbformat.h.
+// The format is the one written to the WAL, so it does not change between versions.
+const TAG_DELETION: u8 = 0x0;
+const TAG_VALUE: u8 = 0x1;
+const TAG_MERGE: u8 = 0x2;
+const TAG_LOG_DATA: u8 = 0x3;
+const TAG_CF_DELETION: u8 = 0x4;
+const TAG_CF_VALUE: u8 = 0x5;
+const TAG_CF_MERGE: u8 = 0x6;
+const TAG_SINGLE_DELETION: u8 = 0x7;
+const TAG_CF_SINGLE_DELETION: u8 = 0x8;
+const TAG_BEGIN_PREPARE_XID: u8 = 0x9;
+const TAG_END_PREPARE_XID: u8 = 0xA;
+const TAG_COMMIT_XID: u8 = 0xB;
+const TAG_ROLLBACK_XID: u8 = 0xC;
+const TAG_NOOP: u8 = 0xD;
+const TAG_CF_RANGE_DELETION: u8 = 0xE;
+const TAG_RANGE_DELETION: u8 = 0xF;
+const TAG_BEGIN_PERSISTED_PREPARE_XID: u8 = 0x12;
+const TAG_BEGIN_UNPREPARE_XID: u8 = 0x13;
+
+// 8 bytes of sequence number followed by a 4 byte count.
+const HEADER_SIZE: usize = 12;
+
+/// Reads the fields of the records that follow the batch header.
+struct BatchReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BatchReader<'a> {
+    fn byte(&mut self) -> Option<u8> {
+        let (&b, rest) = self.data.split_first()?;
+        self.data = rest;
+        Some(b)
+    }
+
+    fn varint32(&mut self) -> Option<u32> {
+        let mut result = 0u32;
+        for shift in (0..5).map(|i| i * 7) {
+            let b = self.byte()?;
+            result |= u32::from(b & 0x7f) << shift;
+            if b & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    fn slice(&mut self) -> Option<&'a [u8]> {
+        let len = self.varint32()? as usize;
+        if len > self.data.len() {
+            return None;
+        }
+        let (slice, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(slice)
+    }
+
+    /// Decodes the record starting with `tag` and passes it to `handler`.
+    /// Returns `None` if the record is malformed or of an unknown kind.
+    fn replay<I: WriteBatchIterator>(&mut self, tag: u8, handler: &mut I) -> Option<()> {
+        let cf_id = match tag {
+            TAG_CF_DELETION | TAG_CF_VALUE | TAG_CF_MERGE | TAG_CF_SINGLE_DELETION
+            | TAG_CF_RANGE_DELETION => self.varint32()?,
+            _ => 0,
+        };
+        match tag {
+            TAG_VALUE | TAG_CF_VALUE => {
+                let key = self.slice()?;
+                let value = self.slice()?;
+                handler.put(cf_id, key, value);
+            }
+            TAG_DELETION | TAG_CF_DELETION | TAG_SINGLE_DELETION | TAG_CF_SINGLE_DELETION => {
+                let key = self.slice()?;
+                handler.delete(cf_id, key);
+            }
+            TAG_MERGE | TAG_CF_MERGE => {
+                let key = self.slice()?;
+                let value = self.slice()?;
+                handler.merge(cf_id, key, value);
+            }
+            TAG_RANGE_DELETION | TAG_CF_RANGE_DELETION => {
+                let from = self.slice()?;
+                let to = self.slice()?;
+                handler.delete_range(cf_id, from, to);
+            }
+            // Markers left by `put_log_data` and two-phase commit carry no data
+            // for the handler.
+            TAG_LOG_DATA | TAG_END_PREPARE_XID | TAG_COMMIT_XID | TAG_ROLLBACK_XID => {
+                self.slice()?;
+            }
+            TAG_BEGIN_PREPARE_XID | TAG_BEGIN_PERSISTED_PREPARE_XID | TAG_BEGIN_UNPREPARE_XID
+            | TAG_NOOP => {}
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
 /// An atomic batch of write operations.
 ///
 /// Making an atomic commit of several writes:
@@ -17,6 +134,48 @@ use libc::{size_t, c_char};
 /// }
 /// ```
 impl WriteBatch {
+    /// Rebuilds a batch from the serialized form returned by `data`.
+    pub fn from_data(data: &[u8]) -> WriteBatch {
+        unsafe {
+            WriteBatch {
+                inner: ffi::rocksdb_writebatch_create_from(
+                    data.as_ptr() as *const c_char,
+                    data.len() as size_t,
+                ),
+            }
+        }
+    }
+
+    /// Return the serialized form of the batch, which can be stored or sent
+    /// elsewhere and turned back into a batch with `from_data`.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let mut batch_size: size_t = 0;
+            let batch_data = ffi::rocksdb_writebatch_data(self.inner, &mut batch_size);
+            slice::from_raw_parts(batch_data as *const u8, batch_size as usize)
+        }
+    }
+
+    /// Replays the operations of the batch, in order, into `handler`.
+    ///
+    /// The batch is decoded from its serialized form, as the C API has no
+    /// iteration that reports column families. Returns a `Corruption` error if
+    /// that form is malformed, e.g. for a batch built by `from_data`; the
+    /// operations before the malformed one have already been replayed.
+    pub fn iterate<I: WriteBatchIterator>(&self, handler: &mut I) -> Result<(), Error> {
+        let data = self.data();
+        if data.len() < HEADER_SIZE {
+            return Err(malformed_batch());
+        }
+        let mut reader = BatchReader { data: &data[HEADER_SIZE..] };
+        while let Some(tag) = reader.byte() {
+            if reader.replay(tag, handler).is_none() {
+                return Err(malformed_batch());
+            }
+        }
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         unsafe { ffi::rocksdb_writebatch_count(self.inner) as usize }
     }
@@ -114,6 +273,38 @@ impl WriteBatch {
             Ok(())
         }
     }
+
+    /// Remove database entries from start key to end key.
+    ///
+    /// Removes the database entries in the range ["begin_key", "end_key"), i.e.,
+    /// including "begin_key" and excluding "end_key". It is not an error if no
+    /// keys exist in the range ["begin_key", "end_key").
+    pub fn delete_range(&mut self, from: &[u8], to: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi::rocksdb_writebatch_delete_range(
+                self.inner,
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn delete_range_cf(&mut self, cf: ColumnFamily, from: &[u8], to: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi::rocksdb_writebatch_delete_range_cf(
+                self.inner,
+                cf.inner,
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+            );
+            Ok(())
+        }
+    }
 }
 
 impl Default for WriteBatch {
@@ -122,6 +313,10 @@ impl Default for WriteBatch {
     }
 }
 
+fn malformed_batch() -> Error {
+    Error::with_kind(ErrorKind::Corruption, "malformed WriteBatch".to_owned())
+}
+
 impl Drop for WriteBatch {
     fn drop(&mut self) {
         unsafe { ffi::rocksdb_writebatch_destroy(self.inner) }
@@ -171,4 +366,53 @@ fn writebatch_works() {
     }
     let opts = Options::default();
     assert!(DB::destroy(&opts, path).is_ok());
-}
\ No newline at end of file
+}
+
+#[test]
+fn writebatch_iterate_test() {
+    #[derive(Default)]
+    struct Recorder {
+        ops: Vec<(&'static str, u32, Vec<u8>, Vec<u8>)>,
+    }
+
+    impl WriteBatchIterator for Recorder {
+        fn put(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+            self.ops.push(("put", cf_id, key.to_vec(), value.to_vec()));
+        }
+        fn delete(&mut self, cf_id: u32, key: &[u8]) {
+            self.ops.push(("delete", cf_id, key.to_vec(), vec![]));
+        }
+        fn merge(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+            self.ops.push(("merge", cf_id, key.to_vec(), value.to_vec()));
+        }
+        fn delete_range(&mut self, cf_id: u32, from: &[u8], to: &[u8]) {
+            self.ops.push(("delete_range", cf_id, from.to_vec(), to.to_vec()));
+        }
+    }
+
+    let mut batch = WriteBatch::default();
+    let _ = batch.put(b"k1", b"v1");
+    let _ = batch.merge(b"k2", b"m2");
+    let _ = batch.delete(b"k1");
+    let _ = batch.delete_range(b"a", b"b");
+
+    // replaying a copy rebuilt from the serialized form gives the same operations
+    let copy = WriteBatch::from_data(batch.data());
+    assert_eq!(copy.len(), 4);
+    assert_eq!(copy.data(), batch.data());
+
+    let mut recorder = Recorder::default();
+    copy.iterate(&mut recorder).unwrap();
+    assert_eq!(recorder.ops, vec![
+        ("put", 0, b"k1".to_vec(), b"v1".to_vec()),
+        ("merge", 0, b"k2".to_vec(), b"m2".to_vec()),
+        ("delete", 0, b"k1".to_vec(), vec![]),
+        ("delete_range", 0, b"a".to_vec(), b"b".to_vec()),
+    ]);
+
+    // a truncated batch is reported instead of being partially replayed silently
+    let truncated = WriteBatch::from_data(&batch.data()[..batch.data().len() - 1]);
+    let mut recorder = Recorder::default();
+    assert!(truncated.iterate(&mut recorder).is_err());
+    assert_eq!(recorder.ops.len(), 3);
+}
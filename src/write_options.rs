@@ -49,6 +49,37 @@ impl WriteOptions {
             ffi::rocksdb_writeoptions_disable_WAL(self.inner, disable as c_int);
         }
     }
+
+    /// If true, writes to column families that no longer exist are ignored
+    /// instead of failing the whole batch.
+    ///
+    /// Default: false
+    pub fn set_ignore_missing_column_families(&mut self, ignore: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_ignore_missing_column_families(self.inner, ignore as c_uchar);
+        }
+    }
+
+    /// If true, a write that would have to wait for a write stall fails right away
+    /// with an `ErrorKind::Incomplete` error, for which `Error::is_write_stall`
+    /// returns true.
+    ///
+    /// Default: false
+    pub fn set_no_slowdown(&mut self, no_slowdown: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_no_slowdown(self.inner, no_slowdown as c_uchar);
+        }
+    }
+
+    /// If true, this write is slowed down first when compaction falls behind,
+    /// to keep high priority writes going. Meant for background writers.
+    ///
+    /// Default: false
+    pub fn set_low_pri(&mut self, low_pri: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_low_pri(self.inner, low_pri as c_uchar);
+        }
+    }
 }
 
 impl Default for WriteOptions {
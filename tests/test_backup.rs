@@ -0,0 +1,75 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::backup::{BackupEngine, BackupEngineOptions, RestoreOptions};
+use rocksdb::{DB, Options};
+use std::fs::remove_dir_all;
+
+#[test]
+pub fn test_backup_and_restore() {
+    const PATH_PREFIX: &str = "_rust_rocksdb_backup_";
+
+    let db_path = format!("{}db", PATH_PREFIX);
+    let backup_path = format!("{}backups", PATH_PREFIX);
+    let restore_path = format!("{}restore", PATH_PREFIX);
+    let _ = remove_dir_all(&db_path);
+    let _ = remove_dir_all(&backup_path);
+    let _ = remove_dir_all(&restore_path);
+
+    let backup_opts = BackupEngineOptions::default();
+    let mut backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+    assert!(backup_engine.get_backup_info().is_empty());
+
+    {
+        let db = DB::open_default(&db_path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        backup_engine.create_new_backup(&db).unwrap();
+
+        db.put(b"k1", b"v2").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        backup_engine.create_new_backup(&db).unwrap();
+    }
+
+    let info = backup_engine.get_backup_info();
+    assert_eq!(info.len(), 2);
+    assert!(info[0].backup_id < info[1].backup_id);
+    for backup in &info {
+        assert!(backup.num_files > 0);
+        assert!(backup.size > 0);
+        assert!(backup.timestamp > 0);
+        assert!(backup_engine.verify_backup(backup.backup_id).is_ok());
+    }
+    assert!(backup_engine.verify_backup(info[1].backup_id + 1).is_err());
+
+    // restore the latest backup
+    {
+        let restore_opts = RestoreOptions::default();
+        backup_engine
+            .restore_from_latest_backup(&restore_path, &restore_path, &restore_opts)
+            .unwrap();
+        let db = DB::open_default(&restore_path).unwrap();
+        assert_eq!(*db.get(b"k1").unwrap().unwrap(), *b"v2");
+        assert_eq!(*db.get(b"k2").unwrap().unwrap(), *b"v2");
+    }
+
+    backup_engine.purge_old_backups(1).unwrap();
+    assert_eq!(backup_engine.get_backup_info().len(), 1);
+
+    let opts = Options::default();
+    assert!(DB::destroy(&opts, &db_path).is_ok());
+    assert!(DB::destroy(&opts, &restore_path).is_ok());
+    let _ = remove_dir_all(&backup_path);
+}
@@ -216,4 +216,26 @@ pub fn test_column_family_with_options() {
     }
 
     assert!(DB::destroy(&Options::default(), path).is_ok());
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn test_multi_get_cf() {
+    let path = "_rust_rocksdb_multi_get_cf";
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, path, &["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf2, b"k2", b"v2").unwrap();
+
+        let values = db.multi_get_cf(&[(cf1, b"k1"), (cf1, b"k2"), (cf2, b"k2")]);
+        assert_eq!(values.len(), 3);
+        assert_eq!(&*values[0].as_ref().unwrap().as_ref().unwrap().to_u8(), b"v1");
+        assert!(values[1].as_ref().unwrap().is_none());
+        assert_eq!(&*values[2].as_ref().unwrap().as_ref().unwrap().to_u8(), b"v2");
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
@@ -0,0 +1,47 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{BottommostLevelCompaction, CompactRangeOptions, DB, Options};
+
+fn files_at_level(db: &DB, level: usize) -> u64 {
+    db.property_value(format!("rocksdb.num-files-at-level{}", level))
+        .unwrap()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+pub fn test_compact_range_opt() {
+    let path = "_rust_rocksdb_compact_range_opt_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(files_at_level(&db, 0), 1);
+
+        let mut compactopts = CompactRangeOptions::default();
+        compactopts.set_exclusive_manual_compaction(true);
+        compactopts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+        compactopts.set_change_level(true);
+        compactopts.set_target_level(2);
+        db.compact_range_opt(None, None, &compactopts);
+        assert_eq!(files_at_level(&db, 0), 0);
+        assert_eq!(files_at_level(&db, 2), 1);
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
@@ -0,0 +1,92 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, IteratorMode, Options};
+use rocksdb::comparator;
+use std::cmp::Ordering;
+
+fn keys(db: &DB) -> Vec<Vec<u8>> {
+    db.iterator(IteratorMode::Start).map(|(k, _)| k.to_vec()).collect()
+}
+
+#[test]
+pub fn test_reverse_bytewise_comparator() {
+    let path = "_rust_rocksdb_reverse_comparator_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_comparator("reverse_bytewise", comparator::reverse_bytewise);
+    {
+        let db = DB::open(&opts, path).unwrap();
+        db.put(b"a", b"1").unwrap();
+        db.put(b"c", b"3").unwrap();
+        db.put(b"b", b"2").unwrap();
+        assert_eq!(keys(&db), vec![b"c".to_vec(), b"b".to_vec(), b"a".to_vec()]);
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_u64_big_endian_prefix_comparator() {
+    let path = "_rust_rocksdb_u64_comparator_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_comparator("u64_big_endian_prefix", comparator::u64_big_endian_prefix);
+
+    let key = |n: u64, suffix: &[u8]| {
+        let mut k = (0..8).rev().map(|i| (n >> (i * 8)) as u8).collect::<Vec<u8>>();
+        k.extend_from_slice(suffix);
+        k
+    };
+    {
+        let db = DB::open(&opts, path).unwrap();
+        db.put(&key(256, b"a"), b"").unwrap();
+        db.put(&key(1, b"b"), b"").unwrap();
+        db.put(&key(1, b"a"), b"").unwrap();
+        db.put(b"x", b"").unwrap();
+        assert_eq!(
+            keys(&db),
+            vec![b"x".to_vec(), key(1, b"a"), key(1, b"b"), key(256, b"a")]
+        );
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_stateful_comparator() {
+    let path = "_rust_rocksdb_stateful_comparator_test";
+    // orders keys by their position in a lookup table, unknown keys last
+    let order: Vec<Vec<u8>> = vec![b"low".to_vec(), b"medium".to_vec(), b"high".to_vec()];
+    let rank = move |key: &[u8]| order.iter().position(|k| &k[..] == key).unwrap_or(usize::max_value());
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_comparator("priority", move |a: &[u8], b: &[u8]| match rank(a).cmp(&rank(b)) {
+        Ordering::Equal => a.cmp(b),
+        other => other,
+    });
+    {
+        let db = DB::open(&opts, path).unwrap();
+        db.put(b"high", b"").unwrap();
+        db.put(b"other", b"").unwrap();
+        db.put(b"low", b"").unwrap();
+        db.put(b"medium", b"").unwrap();
+        assert_eq!(
+            keys(&db),
+            vec![b"low".to_vec(), b"medium".to_vec(), b"high".to_vec(), b"other".to_vec()]
+        );
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
@@ -0,0 +1,81 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, IteratorMode, Options, WriteBatch};
+
+fn keys(iter: rocksdb::DBIterator) -> Vec<Vec<u8>> {
+    iter.map(|(k, _)| k.to_vec()).collect()
+}
+
+#[test]
+pub fn test_delete_range() {
+    let path = "_rust_rocksdb_delete_range";
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let db = DB::open_cf(&opts, path, &["default"]).unwrap();
+        for k in &[b"a1", b"b1", b"b2", b"b3", b"c1"] {
+            db.put(*k, b"v").unwrap();
+        }
+
+        let cf = db.cf_handle("default").unwrap();
+        db.delete_range_cf(cf, b"b", b"c").unwrap();
+
+        assert!(db.get(b"a1").unwrap().is_some());
+        assert!(db.get(b"b1").unwrap().is_none());
+        assert!(db.get(b"b3").unwrap().is_none());
+        assert!(db.get(b"c1").unwrap().is_some());
+        assert_eq!(keys(db.iterator(IteratorMode::Start)), vec![b"a1".to_vec(), b"c1".to_vec()]);
+        assert_eq!(keys(db.iterator(IteratorMode::End)), vec![b"c1".to_vec(), b"a1".to_vec()]);
+
+        // the tombstone must survive a compaction
+        db.compact_range(None, None);
+        assert!(db.get(b"b2").unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_delete_range_cf() {
+    let path = "_rust_rocksdb_delete_range_cf";
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, path, &["tenants"]).unwrap();
+        let cf = db.cf_handle("tenants").unwrap();
+        for k in &[b"t1/1", b"t1/2", b"t2/1", b"t3/1"] {
+            db.put_cf(cf, *k, b"v").unwrap();
+        }
+        db.put(b"t1/1", b"default").unwrap();
+
+        db.delete_range_cf(cf, b"t1/", b"t2/").unwrap();
+        assert!(db.get_cf(cf, b"t1/1").unwrap().is_none());
+        assert!(db.get_cf(cf, b"t1/2").unwrap().is_none());
+        assert!(db.get_cf(cf, b"t2/1").unwrap().is_some());
+        // other column families are untouched
+        assert!(db.get(b"t1/1").unwrap().is_some());
+
+        let mut batch = WriteBatch::default();
+        batch.delete_range_cf(cf, b"t2/", b"t3/").unwrap();
+        batch.put_cf(cf, b"t2/9", b"v").unwrap();
+        db.write(batch).unwrap();
+
+        let iter = db.iterator_cf(cf, IteratorMode::Start).unwrap();
+        assert_eq!(keys(iter), vec![b"t2/9".to_vec(), b"t3/1".to_vec()]);
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
@@ -0,0 +1,63 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, FlushOptions, Options, Property, WriteOptions};
+
+#[test]
+pub fn test_flush() {
+    let path = "_rust_rocksdb_flush_test";
+    {
+        let mut db = DB::open_default(path).unwrap();
+        let cf = db.create_cf("cf1", &Options::default()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf, b"k1", b"v1").unwrap();
+        assert_eq!(db.property_int_value(Property::TotalSstFilesSize).unwrap(), Some(0));
+
+        db.flush().unwrap();
+        assert!(db.property_int_value(Property::TotalSstFilesSize).unwrap().unwrap() > 0);
+        assert_eq!(db.property_int_value_cf(cf, Property::TotalSstFilesSize).unwrap(), Some(0));
+
+        let mut flushopts = FlushOptions::default();
+        flushopts.set_wait(true);
+        db.flush_cf_opt(cf, &flushopts).unwrap();
+        assert!(db.property_int_value_cf(cf, Property::TotalSstFilesSize).unwrap().unwrap() > 0);
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_flush_without_wal() {
+    let path = "_rust_rocksdb_flush_without_wal_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        let mut writeopts = WriteOptions::default();
+        writeopts.disable_wal(true);
+        db.put_opt(b"k1", b"v1", &writeopts).unwrap();
+
+        // a read-only instance only sees what is persisted, and with the WAL
+        // disabled that is nothing until the memtable is flushed
+        {
+            let reader = DB::open_for_read_only(&Options::default(), path, false).unwrap();
+            assert!(reader.get(b"k1").unwrap().is_none());
+        }
+        db.flush().unwrap();
+        {
+            let reader = DB::open_for_read_only(&Options::default(), path, false).unwrap();
+            assert_eq!(&*reader.get(b"k1").unwrap().unwrap(), b"v1");
+        }
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
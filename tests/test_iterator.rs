@@ -15,6 +15,7 @@
 extern crate rocksdb;
 
 use rocksdb::{DB, Direction, IteratorMode, MemtableFactory, Options};
+use rocksdb::DBIterator;
 
 fn cba(input: &Box<[u8]>) -> Box<[u8]> {
     input.iter().cloned().collect::<Vec<_>>().into_boxed_slice()
@@ -244,3 +245,50 @@ pub fn test_full_iterator() {
     let opts = Options::default();
     assert!(DB::destroy(&opts, path).is_ok());
 }
+
+#[test]
+pub fn test_range_iterator() {
+    let path = "_rust_rocksdb_range_iterator_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        for k in &[b"a", b"b", b"c", b"d", b"e"] {
+            db.put(*k, *k).unwrap();
+        }
+        let keys = |iter: DBIterator| iter.map(|(k, _)| k.to_vec()).collect::<Vec<_>>();
+
+        assert_eq!(keys(db.range_iterator(&b"b"[..]..&b"d"[..])), vec![b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(
+            keys(db.range_iterator(&b"b"[..]..=&b"d"[..])),
+            vec![b"b".to_vec(), b"c".to_vec(), b"d".to_vec()]
+        );
+        assert_eq!(keys(db.range_iterator(..&b"b"[..])), vec![b"a".to_vec()]);
+        assert_eq!(keys(db.range_iterator(&b"d"[..]..)), vec![b"d".to_vec(), b"e".to_vec()]);
+
+        // walking backwards stays within the range
+        let mut iter = db.range_iterator(&b"b"[..]..&b"d"[..]);
+        iter.set_mode(IteratorMode::End);
+        assert_eq!(keys(iter), vec![b"c".to_vec(), b"b".to_vec()]);
+    }
+    let opts = Options::default();
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_range_iterator_outlives_bounds() {
+    let path = "_rust_rocksdb_iterate_bounds_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        for k in &[b"a", b"b", b"c", b"d"] {
+            db.put(*k, *k).unwrap();
+        }
+        // the iterator keeps its own copy of the bounds
+        let iter = {
+            let lower = b"b".to_vec();
+            let upper = b"d".to_vec();
+            db.range_iterator(&lower[..]..&upper[..])
+        };
+        assert_eq!(iter.map(|(k, _)| k.to_vec()).collect::<Vec<_>>(), vec![b"b".to_vec(), b"c".to_vec()]);
+    }
+    let opts = Options::default();
+    assert!(DB::destroy(&opts, path).is_ok());
+}
@@ -0,0 +1,60 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{OptimisticTransactionDB, OptimisticTransactionOptions, IteratorMode, Options, WriteOptions};
+
+#[test]
+pub fn test_optimistic_transaction_commit() {
+    let path = "_rust_rocksdb_optimistic_txn_commit";
+    {
+        let db = OptimisticTransactionDB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut txn = db.begin(&WriteOptions::default(), &OptimisticTransactionOptions::default()).unwrap();
+        assert_eq!(&*txn.get(b"k1").unwrap().unwrap(), b"v1");
+        txn.put(b"k2", b"v2").unwrap();
+        assert_eq!(&*txn.get(b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k2").unwrap().is_none());
+        txn.commit().unwrap();
+
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert_eq!(db.iterator(IteratorMode::Start).count(), 2);
+    }
+    assert!(OptimisticTransactionDB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_optimistic_transaction_conflict() {
+    let path = "_rust_rocksdb_optimistic_txn_conflict";
+    {
+        let db = OptimisticTransactionDB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut txn = db.begin(&WriteOptions::default(), &OptimisticTransactionOptions::default()).unwrap();
+        assert_eq!(&*txn.get_for_update(b"k1", true).unwrap().unwrap(), b"v1");
+        txn.put(b"k1", b"from txn").unwrap();
+
+        // a concurrent write to a key read by the transaction
+        db.put(b"k1", b"from db").unwrap();
+
+        match txn.commit() {
+            Ok(_) => panic!("commit should detect the conflict"),
+            Err(e) => assert!(e.is_busy()),
+        }
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"from db");
+    }
+    assert!(OptimisticTransactionDB::destroy(&Options::default(), path).is_ok());
+}
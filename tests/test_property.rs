@@ -0,0 +1,53 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, Options, Property};
+
+#[test]
+pub fn test_property() {
+    let path = "_rust_rocksdb_property_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        assert!(db.property_value(Property::Stats).unwrap().unwrap().len() > 0);
+        assert!(db.property_value("rocksdb.no-such-property").unwrap().is_none());
+        assert!(db.property_value("bad\0name").is_err());
+
+        assert_eq!(db.property_int_value(Property::EstimateNumKeys).unwrap(), Some(1));
+        assert!(db.property_int_value(Property::CurSizeAllMemTables).unwrap().unwrap() > 0);
+        assert_eq!(db.property_int_value(Property::NumRunningCompactions).unwrap(), Some(0));
+        assert!(db.property_int_value(Property::Stats).unwrap().is_none());
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_property_cf() {
+    let path = "_rust_rocksdb_property_cf_test";
+    {
+        let mut db = DB::open_default(path).unwrap();
+        let cf = db.create_cf("cf1", &Options::default()).unwrap();
+        db.put_cf(cf, b"k1", b"v1").unwrap();
+        db.put_cf(cf, b"k2", b"v2").unwrap();
+
+        assert_eq!(db.property_int_value_cf(cf, Property::EstimateNumKeys).unwrap(), Some(2));
+        assert_eq!(db.property_int_value(Property::EstimateNumKeys).unwrap(), Some(0));
+        assert!(db.property_value_cf(cf, Property::Stats).unwrap().is_some());
+        assert_eq!(db.property_int_value_cf(cf, Property::TotalSstFilesSize).unwrap(), Some(0));
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
@@ -152,3 +152,45 @@ pub fn test_seek_for_prev() {
     assert_eq!(iter.key(), Some(b"k2".to_vec()));
     assert_eq!(iter.value(), Some(b"v2".to_vec()));
 }
+
+#[test]
+pub fn test_borrowed_item() {
+    let db = setup_test_db("borrowed_item");
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+
+    let mut iter = db.raw_iterator();
+    iter.seek_to_first();
+    assert_eq!(iter.item(), Some((&b"k1"[..], &b"v1"[..])));
+    iter.next();
+    assert_eq!(iter.item(), Some((&b"k2"[..], &b"v2"[..])));
+    iter.next();
+    assert_eq!(iter.item(), None);
+}
+
+#[test]
+pub fn test_for_each() {
+    let db = setup_test_db("for_each");
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.put(b"k3", b"v3").unwrap();
+
+    let mut iter = db.raw_iterator();
+    iter.seek(b"k2");
+    let mut total = 0;
+    iter.for_each(|key, value| {
+        total += key.len() + value.len();
+        true
+    });
+    assert_eq!(total, 8);
+    assert!(!iter.valid());
+
+    let mut seen = Vec::new();
+    iter.seek_to_first();
+    iter.for_each(|key, _| {
+        seen.push(key.to_vec());
+        seen.len() < 2
+    });
+    assert_eq!(seen, vec![b"k1".to_vec(), b"k2".to_vec()]);
+    assert_eq!(iter.key(), Some(b"k2".to_vec()));
+}
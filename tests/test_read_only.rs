@@ -0,0 +1,68 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, ErrorKind, Options};
+use std::env;
+use std::process::{Child, Command};
+
+// The read-only instance runs in a child process, started by running the
+// ignored test below from this same test binary.
+const PRIMARY_PATH_VAR: &str = "_RUST_ROCKSDB_PRIMARY_PATH";
+
+fn spawn_child(test_name: &str, primary_path: &str) -> Child {
+    Command::new(env::current_exe().unwrap())
+        .args(&[test_name, "--exact", "--ignored", "--nocapture", "--test-threads=1"])
+        .env(PRIMARY_PATH_VAR, primary_path)
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+pub fn test_read_only_instance() {
+    let path = "_rust_rocksdb_read_only_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        // not flushed, but replayed from the WAL by the read-only instance
+        db.put(b"k2", b"v2").unwrap();
+
+        let status = spawn_child("read_only_child", path).wait().unwrap();
+        assert!(status.success());
+
+        // a non-empty WAL is refused when asked to
+        let err = DB::open_for_read_only(&Options::default(), path, true).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Corruption);
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+#[ignore]
+pub fn read_only_child() {
+    let path = match env::var(PRIMARY_PATH_VAR) {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+    let db = DB::open_for_read_only(&Options::default(), &path, false).unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+
+    let err = db.put(b"k3", b"v3").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotSupported);
+    assert!(db.delete(b"k1").is_err());
+    assert!(db.flush().is_err());
+}
@@ -0,0 +1,83 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, DBIterator, DBRawIterator, ErrorKind, IteratorMode, Options, ReadOptions, ReadTier};
+
+#[test]
+pub fn test_block_cache_read_tier() {
+    let path = "_rust_rocksdb_read_tier_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    {
+        // the memtable was flushed on reopen, so the value is only on disk
+        let db = DB::open_default(path).unwrap();
+        let mut cache_only = ReadOptions::default();
+        cache_only.set_read_tier(ReadTier::BlockCache);
+        match db.get_opt(b"k1", &cache_only) {
+            Err(e) => assert_eq!(e.kind(), ErrorKind::Incomplete),
+            Ok(v) => panic!("expected an Incomplete error, got {:?}", v.map(|v| v.to_vec())),
+        }
+
+        // a regular read brings it into the block cache
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get_opt(b"k1", &cache_only).unwrap().unwrap(), b"v1");
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_scan_read_options() {
+    let path = "_rust_rocksdb_scan_read_options_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_fill_cache(false);
+        readopts.set_verify_checksums(true);
+        readopts.set_readahead_size(2 * 1024 * 1024);
+        readopts.set_pin_data(true);
+        readopts.set_ignore_range_deletions(true);
+        readopts.set_max_skippable_internal_keys(0);
+        let iter = DBIterator::new(&db, &readopts, IteratorMode::Start);
+        assert_eq!(iter.count(), 2);
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_tailing_iterator() {
+    let path = "_rust_rocksdb_tailing_iterator_test";
+    {
+        let db = DB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_tailing(true);
+        let mut iter = DBRawIterator::new(&db, &readopts);
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(b"k1".to_vec()));
+
+        // a tailing iterator sees writes made after it was created
+        db.put(b"k2", b"v2").unwrap();
+        iter.seek(b"k2");
+        assert_eq!(iter.value(), Some(b"v2".to_vec()));
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
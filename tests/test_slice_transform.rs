@@ -46,3 +46,63 @@ pub fn test_slice_transform() {
         assert_eq!(b_iterator.collect::<Vec<_>>(), expected)
     }
 }
+
+#[test]
+pub fn test_delimited_prefix_slice_transform() {
+    let path = "_rust_rocksdb_delimited_prefix_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_prefix_extractor(SliceTransform::create_delimited_prefix(0));
+    {
+        let db = DB::open(&opts, path).unwrap();
+        assert!(db.put(b"acme\0users\x001", b"1").is_ok());
+        assert!(db.put(b"acme\0users\x002", b"2").is_ok());
+        assert!(db.put(b"globex\0users\x001", b"3").is_ok());
+
+        let values: Vec<Box<[u8]>> = db.prefix_iterator(b"acme\0").map(|(_, v)| v).collect();
+        assert_eq!(values, vec![b"1".to_vec().into_boxed_slice(), b"2".to_vec().into_boxed_slice()]);
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_closure_slice_transform() {
+    let path = "_rust_rocksdb_closure_slicetransform_test";
+    // the prefix length is only known at runtime
+    let len = "ab".len();
+    let prefix_extractor = SliceTransform::create_with_domain(
+        "runtime_len",
+        move |k: &[u8]| k[..len].to_vec(),
+        move |k: &[u8]| k.len() >= len,
+    );
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_prefix_extractor(prefix_extractor);
+    {
+        let db = DB::open(&opts, path).unwrap();
+        assert!(db.put(b"ab1", b"1").is_ok());
+        assert!(db.put(b"ab2", b"2").is_ok());
+        assert!(db.put(b"ac1", b"3").is_ok());
+        assert_eq!(db.prefix_iterator(b"ab").count(), 2);
+        assert_eq!(db.prefix_iterator(b"ac").count(), 1);
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_capped_prefix_slice_transform() {
+    let path = "_rust_rocksdb_capped_prefix_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_prefix_extractor(SliceTransform::create_capped_prefix(3));
+    {
+        let db = DB::open(&opts, path).unwrap();
+        assert!(db.put(b"a", b"1").is_ok());
+        assert!(db.put(b"abc1", b"2").is_ok());
+        assert!(db.put(b"abc2", b"3").is_ok());
+        assert_eq!(db.prefix_iterator(b"abc").count(), 2);
+        assert_eq!(&*db.get(b"a").unwrap().unwrap(), b"1");
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
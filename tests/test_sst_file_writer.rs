@@ -0,0 +1,103 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, IngestExternalFileOptions, Options, SstFileWriter};
+use std::fs;
+use std::path::Path;
+
+#[test]
+pub fn test_sst_file_writer_and_ingest() {
+    let path = "_rust_rocksdb_sst_ingest_test";
+    let dir = "_rust_rocksdb_sst_files";
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    let first = format!("{}/first.sst", dir);
+    let second = format!("{}/second.sst", dir);
+
+    let opts = Options::default();
+    {
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(&first).unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.delete(b"k3").unwrap();
+        // keys must be added in order
+        assert!(writer.put(b"k0", b"v0").is_err());
+        writer.finish().unwrap();
+        assert!(writer.file_size() > 0);
+
+        writer.open(&second).unwrap();
+        writer.put(b"k4", b"v4").unwrap();
+        writer.finish().unwrap();
+    }
+
+    {
+        let mut db = DB::open_default(path).unwrap();
+        db.put(b"k3", b"old").unwrap();
+        db.ingest_external_file(&[&first]).unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k3").unwrap().is_none());
+
+        let cf = db.create_cf("cf1", &Options::default()).unwrap();
+        let mut ingestopts = IngestExternalFileOptions::default();
+        ingestopts.set_move_files(true);
+        ingestopts.set_snapshot_consistency(true);
+        ingestopts.set_allow_global_seqno(true);
+        ingestopts.set_allow_blocking_flush(true);
+        db.ingest_external_file_cf_opt(cf, &[&second], &ingestopts).unwrap();
+        assert_eq!(&*db.get_cf(cf, b"k4").unwrap().unwrap(), b"v4");
+        assert!(db.get(b"k4").unwrap().is_none());
+        assert!(!Path::new(&second).exists());
+
+        assert!(db.ingest_external_file(&[format!("{}/missing.sst", dir)]).is_err());
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+pub fn test_ingest_behind() {
+    let path = "_rust_rocksdb_sst_ingest_behind_test";
+    let file = "_rust_rocksdb_sst_ingest_behind.sst";
+
+    let opts = Options::default();
+    {
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(file).unwrap();
+        writer.put(b"k1", b"from file").unwrap();
+        writer.put(b"k2", b"from file").unwrap();
+        writer.finish().unwrap();
+    }
+
+    let mut dbopts = Options::default();
+    dbopts.create_if_missing(true);
+    dbopts.set_allow_ingest_behind(true);
+    dbopts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+    {
+        let db = DB::open(&dbopts, path).unwrap();
+        db.put(b"k1", b"from db").unwrap();
+
+        let mut ingestopts = IngestExternalFileOptions::default();
+        ingestopts.set_ingest_behind(true);
+        db.ingest_external_file_opt(&[file], &ingestopts).unwrap();
+        // existing keys take precedence over ingested ones
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"from db");
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"from file");
+    }
+    assert!(DB::destroy(&dbopts, path).is_ok());
+    let _ = fs::remove_file(file);
+}
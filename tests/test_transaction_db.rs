@@ -0,0 +1,201 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{TransactionDB, TransactionDBOptions, TransactionOptions, Options, ColumnFamilyDescriptor, WriteOptions,
+              Direction, IteratorMode, MergeOperands};
+
+#[test]
+pub fn test_transaction_db_column_family() {
+    let path = "_rust_rocksdb_txn_cftest";
+
+    // should be able to create column families
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let txopts = TransactionDBOptions::default();
+        let mut db = TransactionDB::open(&opts, &txopts, path).unwrap();
+        let opts = Options::default();
+        match db.create_cf("cf1", &opts) {
+            Ok(_) => println!("cf1 created successfully"),
+            Err(e) => {
+                panic!("could not create column family: {}", e);
+            }
+        }
+        assert!(db.cf_handle("cf1").is_some());
+    }
+
+    // should fail to open db without specifying same column families
+    {
+        let txopts = TransactionDBOptions::default();
+        match TransactionDB::open(&Options::default(), &txopts, path) {
+            Ok(_) => {
+                panic!("should not have opened DB successfully without \
+                        specifying column families")
+            }
+            Err(e) => {
+                assert!(e.to_string()
+                    .starts_with("Invalid argument: You have to open all \
+                                  column families."))
+            }
+        }
+    }
+
+    // should properly open db when specifying all column families
+    {
+        let txopts = TransactionDBOptions::default();
+        let db = TransactionDB::open_cf(&Options::default(), &txopts, path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert!(db.put_cf(cf1, b"k1", b"v1").is_ok());
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+
+    // should see the data written through a transaction on reopen
+    {
+        let cf = ColumnFamilyDescriptor::new("cf1", Options::default());
+        let txopts = TransactionDBOptions::default();
+        let db = TransactionDB::open_cf_descriptors(&Options::default(), &txopts, path, vec![cf]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+    }
+
+    assert!(TransactionDB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_snapshot() {
+    let path = "_rust_rocksdb_txn_snapshot";
+    {
+        let db = TransactionDB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut txn_opts = TransactionOptions::default();
+        txn_opts.set_snapshot(true);
+        let mut txn = db.begin(&WriteOptions::default(), &txn_opts).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        txn.put(b"k3", b"v3").unwrap();
+        {
+            let snapshot = txn.get_snapshot().unwrap();
+            assert_eq!(&*snapshot.get(b"k1").unwrap().unwrap(), b"v1");
+            assert!(snapshot.get(b"k2").unwrap().is_none());
+            assert_eq!(&*snapshot.get(b"k3").unwrap().unwrap(), b"v3");
+        }
+        txn.rollback().unwrap();
+    }
+    assert!(TransactionDB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_multi_get() {
+    let path = "_rust_rocksdb_txn_multi_get";
+    {
+        let db = TransactionDB::open_default(path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut txn = db.begin(&WriteOptions::default(), &TransactionOptions::default()).unwrap();
+        txn.put(b"k2", b"v2").unwrap();
+        let values = txn.multi_get(&[b"k1", b"k2", b"k3"]);
+        assert_eq!(&*values[0].as_ref().unwrap().as_ref().unwrap().to_u8(), b"v1");
+        assert_eq!(&*values[1].as_ref().unwrap().as_ref().unwrap().to_u8(), b"v2");
+        assert!(values[2].as_ref().unwrap().is_none());
+        txn.rollback().unwrap();
+    }
+    assert!(TransactionDB::destroy(&Options::default(), path).is_ok());
+}
+
+fn concat_merge(_key: &[u8], existing_val: Option<&[u8]>, operands: &mut MergeOperands) -> Option<Vec<u8>> {
+    let mut result = existing_val.map(|v| v.to_vec()).unwrap_or_default();
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    Some(result)
+}
+
+fn open_with_cf(path: &str) -> TransactionDB {
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_merge_operator("concat", concat_merge, None);
+    let cf = ColumnFamilyDescriptor::new("cf1", cf_opts);
+    TransactionDB::open_cf_descriptors(&opts, &TransactionDBOptions::default(), path, vec![cf]).unwrap()
+}
+
+#[test]
+pub fn test_transaction_cf_writes() {
+    let path = "_rust_rocksdb_txn_cf_writes";
+    {
+        let db = open_with_cf(path);
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"a").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+
+        let mut txn = db.begin(&WriteOptions::default(), &TransactionOptions::default()).unwrap();
+        assert_eq!(&*txn.get_for_update_cf(cf1, b"k1", true).unwrap().unwrap(), b"a");
+        txn.merge_cf(cf1, b"k1", b"b").unwrap();
+        txn.delete_cf(cf1, b"k2").unwrap();
+        assert_eq!(&*txn.get_cf(cf1, b"k1").unwrap().unwrap(), b"ab");
+        assert!(txn.get_cf(cf1, b"k2").unwrap().is_none());
+        // not visible outside the transaction until it commits
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"a");
+        txn.commit().unwrap();
+
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"ab");
+        assert!(db.get_cf(cf1, b"k2").unwrap().is_none());
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+    assert!(TransactionDB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_transaction_iterator_cf() {
+    let path = "_rust_rocksdb_txn_iterator_cf";
+    {
+        let db = open_with_cf(path);
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k3", b"v3").unwrap();
+        db.put(b"k0", b"v0").unwrap();
+
+        let mut txn = db.begin(&WriteOptions::default(), &TransactionOptions::default()).unwrap();
+        txn.put_cf(cf1, b"k2", b"v2").unwrap();
+        txn.delete_cf(cf1, b"k3").unwrap();
+        txn.put(b"k4", b"v4").unwrap();
+
+        let keys: Vec<_> = txn.iterator_cf(cf1, IteratorMode::Start).map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![b"k1".to_vec().into_boxed_slice(), b"k2".to_vec().into_boxed_slice()]);
+
+        let keys: Vec<_> = txn.iterator_cf(cf1, IteratorMode::From(b"k2", Direction::Reverse))
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![b"k2".to_vec().into_boxed_slice(), b"k1".to_vec().into_boxed_slice()]);
+
+        let keys: Vec<_> = txn.iterator(IteratorMode::Start).map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![b"k0".to_vec().into_boxed_slice(), b"k4".to_vec().into_boxed_slice()]);
+
+        {
+            let mut raw = txn.raw_iterator_cf(cf1);
+            raw.seek_to_last();
+            assert_eq!(raw.item(), Some((&b"k2"[..], &b"v2"[..])));
+        }
+
+        // the iterator outside the transaction does not see its writes
+        let keys: Vec<_> = db.iterator_cf(cf1, IteratorMode::Start).unwrap().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![b"k1".to_vec().into_boxed_slice(), b"k3".to_vec().into_boxed_slice()]);
+        txn.rollback().unwrap();
+    }
+    assert!(TransactionDB::destroy(&Options::default(), path).is_ok());
+}
@@ -0,0 +1,58 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, ErrorKind, Options};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+#[test]
+pub fn test_open_with_ttl() {
+    let path = "_rust_rocksdb_ttl_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    {
+        let db = DB::open_with_ttl(&opts, path, Duration::from_secs(1)).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        let written = now_secs();
+        db.compact_range(None, None);
+        // not expired yet, and the stored timestamp is not part of the value
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        // RocksDB drops an entry once `timestamp + ttl < now`, in whole seconds
+        while now_secs() < written + 2 {
+            thread::sleep(Duration::from_millis(50));
+        }
+        db.put(b"k2", b"v2").unwrap();
+        db.compact_range(None, None);
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}
+
+#[test]
+pub fn test_open_with_sub_second_ttl() {
+    let path = "_rust_rocksdb_sub_second_ttl_test";
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    // rejected before anything is created at `path`
+    let err = DB::open_with_ttl(&opts, path, Duration::from_millis(500)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+}
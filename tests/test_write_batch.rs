@@ -0,0 +1,65 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{DB, Options, WriteBatch, WriteBatchIterator};
+
+#[derive(Default)]
+struct Recorder {
+    ops: Vec<(&'static str, u32, Vec<u8>)>,
+}
+
+impl WriteBatchIterator for Recorder {
+    fn put(&mut self, cf_id: u32, key: &[u8], _value: &[u8]) {
+        self.ops.push(("put", cf_id, key.to_vec()));
+    }
+    fn delete(&mut self, cf_id: u32, key: &[u8]) {
+        self.ops.push(("delete", cf_id, key.to_vec()));
+    }
+    fn merge(&mut self, cf_id: u32, key: &[u8], _value: &[u8]) {
+        self.ops.push(("merge", cf_id, key.to_vec()));
+    }
+    fn delete_range(&mut self, cf_id: u32, from: &[u8], _to: &[u8]) {
+        self.ops.push(("delete_range", cf_id, from.to_vec()));
+    }
+}
+
+#[test]
+pub fn test_write_batch_iterate_cf() {
+    let path = "_rust_rocksdb_write_batch_iterate_cf";
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, path, &["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k1", b"v1").unwrap();
+        batch.put_cf(cf1, b"k2", b"v2").unwrap();
+        batch.delete_cf(cf1, b"k3").unwrap();
+        batch.delete_range_cf(cf1, b"a", b"b").unwrap();
+
+        let mut recorder = Recorder::default();
+        batch.iterate(&mut recorder).unwrap();
+        assert_eq!(recorder.ops, vec![
+            ("put", 0, b"k1".to_vec()),
+            ("put", 1, b"k2".to_vec()),
+            ("delete", 1, b"k3".to_vec()),
+            ("delete_range", 1, b"a".to_vec()),
+        ]);
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
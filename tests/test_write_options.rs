@@ -0,0 +1,105 @@
+// Copyright 2019 Ramon Navarro Bosch
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate rocksdb;
+
+use rocksdb::{CompactionDecision, DB, ErrorKind, Options, WriteBatch, WriteOptions};
+use std::sync::{Arc, Condvar, Mutex};
+
+// Opens the gate when dropped, so that a failed assertion does not leave the
+// compaction, and the DB waiting for it on close, blocked forever.
+struct OpenGate(Arc<(Mutex<bool>, Condvar)>);
+
+impl Drop for OpenGate {
+    fn drop(&mut self) {
+        let &(ref open, ref cvar) = &*self.0;
+        *open.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        cvar.notify_all();
+    }
+}
+
+#[test]
+pub fn test_ignore_missing_column_families() {
+    let path = "_rust_rocksdb_ignore_missing_cf_test";
+    {
+        let mut db = DB::open_default(path).unwrap();
+        let cf = db.create_cf("cf1", &Options::default()).unwrap();
+
+        let batch = || {
+            let mut batch = WriteBatch::default();
+            batch.put_cf(cf, b"k1", b"v1").unwrap();
+            batch.put(b"k2", b"v2").unwrap();
+            batch
+        };
+        let first = batch();
+        let second = batch();
+        db.drop_cf("cf1").unwrap();
+
+        let err = db.write_opt(first, &WriteOptions::default()).unwrap_err();
+        assert!(!err.is_write_stall());
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        let mut writeopts = WriteOptions::default();
+        writeopts.set_ignore_missing_column_families(true);
+        db.write_opt(second, &writeopts).unwrap();
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+    assert!(DB::destroy(&Options::default(), path).is_ok());
+}
+
+#[test]
+pub fn test_no_slowdown_low_pri_writes() {
+    let path = "_rust_rocksdb_no_slowdown_test";
+    // holds the compaction that would clear the stall until the test is done
+    let gate = Arc::new((Mutex::new(false), Condvar::new()));
+    let filter_gate = gate.clone();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_level_zero_file_num_compaction_trigger(2);
+    opts.set_level_zero_slowdown_writes_trigger(2);
+    opts.set_level_zero_stop_writes_trigger(2);
+    opts.set_compaction_filter("gate", move |_level: u32, _key: &[u8], _value: &[u8], _manual: bool| {
+        let &(ref open, ref cvar) = &*filter_gate;
+        let mut open = open.lock().unwrap();
+        while !*open {
+            open = cvar.wait(open).unwrap();
+        }
+        CompactionDecision::Keep
+    });
+    {
+        let db = DB::open(&opts, path).unwrap();
+        // dropped before `db`
+        let _open_gate = OpenGate(gate.clone());
+        let mut writeopts = WriteOptions::default();
+        writeopts.set_no_slowdown(true);
+        // nothing is stalled yet, so the write goes through
+        db.put_opt(b"k1", b"v1", &writeopts).unwrap();
+        db.flush().unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+
+        // two level 0 files stop writes until they are compacted
+        let err = db.put_opt(b"k3", b"v3", &writeopts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Incomplete);
+        assert!(err.is_write_stall());
+
+        writeopts.set_low_pri(true);
+        let err = db.put_opt(b"k3", b"v3", &writeopts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Incomplete);
+        assert!(err.is_write_stall());
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+    assert!(DB::destroy(&opts, path).is_ok());
+}